@@ -1,7 +1,7 @@
 use super::{BoardPiece, PColor, Piece};
 use ggez::graphics;
 use gridit::pattern::SideStepsPattern;
-use gridit::{Grid, Position, PositionsEnumerator};
+use gridit::{Grid, Position, PositionsEnumerator, Symmetry};
 
 pub struct Knight {
     img: graphics::Image,
@@ -20,17 +20,7 @@ impl Piece for Knight {
     }
 
     fn possible_moves(&self, grid: &Grid<BoardPiece>, pos: Position) -> Vec<Position> {
-        let steps: Vec<(i32, i32)> = vec![
-            (1, 2),
-            (2, 1),
-            (-1, 2),
-            (1, -2),
-            (2, -1),
-            (-2, 1),
-            (-1, -2),
-            (-2, -1),
-        ];
-        let pattern = SideStepsPattern::new(steps);
+        let pattern = SideStepsPattern::with_symmetry(vec![(1, 2)], Symmetry::All);
         grid.pattern(pos, pattern)
             .grid_positions()
             .filter(|(_, o)| !self.same_pcolor(o))
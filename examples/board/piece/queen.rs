@@ -1,6 +1,6 @@
 use super::{BoardPiece, PColor, Piece};
 use ggez::graphics;
-use gridit::pattern::{DirectionPattern, Repeat};
+use gridit::pattern::{DirectionPattern, Pattern, Repeat};
 use gridit::{Grid, Position, PositionsEnumerator};
 
 pub struct Queen {
@@ -20,37 +20,14 @@ impl Piece for Queen {
     }
 
     fn possible_moves(&self, grid: &Grid<BoardPiece>, pos: Position) -> Vec<Position> {
-        let patterns = [
-            DirectionPattern::new((0, 1), Repeat::TillEnd),
-            DirectionPattern::new((0, -1), Repeat::TillEnd),
-            DirectionPattern::new((1, 0), Repeat::TillEnd),
-            DirectionPattern::new((-1, 0), Repeat::TillEnd),
-            DirectionPattern::new((1, 1), Repeat::TillEnd),
-            DirectionPattern::new((-1, -1), Repeat::TillEnd),
-            DirectionPattern::new((1, -1), Repeat::TillEnd),
-            DirectionPattern::new((-1, 1), Repeat::TillEnd),
-        ];
-
-        patterns
-            .iter()
-            .map(|pattern| {
-                let mut prev: &Option<Box<dyn Piece + 'static>> = &None;
-                grid.pattern(pos, *pattern)
-                    .grid_positions()
-                    .take_while(|(_, o)| {
-                        if matches!(prev, Some(p) if p.pcolor() != self.pcolor) {
-                            return false;
-                        }
-                        if self.same_pcolor(o) {
-                            return false;
-                        }
-                        prev = &o;
-                        true
-                    })
-                    .map(|(p, _)| p)
-                    .collect::<Vec<Position>>()
-            })
-            .flatten()
+        let rook = DirectionPattern::new((1, 0), Repeat::TillEnd).rotations4();
+        let bishop = DirectionPattern::new((1, 1), Repeat::TillEnd).rotations4();
+        let pattern = rook.chain(bishop);
+        grid.pattern(pos, pattern)
+            .until_blocked_inclusive(|o, _| o.is_some())
+            .grid_positions()
+            .filter(|(_, o)| !self.same_pcolor(o))
+            .map(|(pos, _)| pos)
             .collect()
     }
 
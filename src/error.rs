@@ -0,0 +1,124 @@
+use crate::grid::Position;
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by the fallible `try_` functions of [Grid](crate::Grid).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// Width or height of the grid is zero.
+    ZeroSize { width: usize, height: usize },
+
+    /// Width times height does not fit into usize.
+    TooLarge { width: usize, height: usize },
+
+    /// The number of items does not equal width times height.
+    LengthMismatch {
+        len: usize,
+        width: usize,
+        height: usize,
+    },
+
+    /// A position is outside of a grid with the given width and height.
+    OutOfBounds {
+        position: Position,
+        width: usize,
+        height: usize,
+    },
+
+    /// Line `line` (counted from zero) of a text map does not have the length of the first line.
+    RaggedLine {
+        line: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::ZeroSize { width, height } => write!(
+                f,
+                "width and height can not be zero, got {}x{}",
+                width, height
+            ),
+            GridError::TooLarge { width, height } => write!(
+                f,
+                "width * height of {}x{} does not fit into usize",
+                width, height
+            ),
+            GridError::LengthMismatch { len, width, height } => write!(
+                f,
+                "length {} does not equal width * height of {}x{}",
+                len, width, height
+            ),
+            GridError::OutOfBounds {
+                position,
+                width,
+                height,
+            } => write!(
+                f,
+                "position ({}, {}) is out of bounds of {}x{} grid",
+                position.x, position.y, width, height
+            ),
+            GridError::RaggedLine { line, len, width } => write!(
+                f,
+                "line {} has length {}, expected width {}",
+                line, len, width
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// Error returned when parsing a piece in Betza notation fails,
+/// see [Betza](crate::pattern::Betza). Every `idx` is the index of a char in the notation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BetzaError {
+    /// The notation is empty.
+    Empty,
+
+    /// A char which is neither an atom nor a modifier.
+    UnknownChar { idx: usize, c: char },
+
+    /// Modifiers at the end of the notation which are not followed by an atom.
+    MissingAtom { idx: usize },
+
+    /// A range which does not fit into usize.
+    InvalidRange { idx: usize },
+
+    /// A range after a rider like `R` or `WW`, which has an unlimited range.
+    RangeOnRider { idx: usize },
+
+    /// The direction modifiers of the atom at `idx` exclude all of its directions.
+    NoDirection { idx: usize },
+}
+
+impl fmt::Display for BetzaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BetzaError::Empty => write!(f, "betza notation is empty"),
+            BetzaError::UnknownChar { idx, c } => {
+                write!(
+                    f,
+                    "unknown char '{}' at {}, expected an atom or a modifier",
+                    c, idx
+                )
+            }
+            BetzaError::MissingAtom { idx } => {
+                write!(f, "missing atom after the modifiers at {}", idx)
+            }
+            BetzaError::InvalidRange { idx } => write!(f, "range at {} is too large", idx),
+            BetzaError::RangeOnRider { idx } => {
+                write!(f, "range at {} follows a rider, which has no range", idx)
+            }
+            BetzaError::NoDirection { idx } => write!(
+                f,
+                "modifiers exclude every direction of the atom at {}",
+                idx
+            ),
+        }
+    }
+}
+
+impl Error for BetzaError {}
@@ -0,0 +1,343 @@
+use crate::grid::{Grid, Position};
+use crate::iter::{FovIter, PositionList};
+
+// A slope num/den of a line from the origin, den is always positive
+#[derive(Copy, Clone, Debug)]
+struct Slope {
+    num: isize,
+    den: isize,
+}
+
+impl Slope {
+    // The slope to the left edge of the tile at `col` in row `depth`
+    fn of(depth: isize, col: isize) -> Self {
+        Self {
+            num: 2 * col - 1,
+            den: 2 * depth,
+        }
+    }
+}
+
+// A row of tiles at `depth` between the slopes `start` and `end`
+#[derive(Copy, Clone, Debug)]
+struct Row {
+    depth: isize,
+    start: Slope,
+    end: Slope,
+}
+
+impl Row {
+    // depth * start rounded with ties up
+    fn min_col(&self) -> isize {
+        (2 * self.depth * self.start.num + self.start.den).div_euclid(2 * self.start.den)
+    }
+
+    // depth * end rounded with ties down
+    fn max_col(&self) -> isize {
+        -(-(2 * self.depth * self.end.num - self.end.den)).div_euclid(2 * self.end.den)
+    }
+
+    // A floor tile is only visible if its center lies between the slopes,
+    // which makes the field of view symmetric.
+    fn is_symmetric(&self, col: isize) -> bool {
+        col * self.start.den >= self.depth * self.start.num
+            && col * self.end.den <= self.depth * self.end.num
+    }
+
+    fn next(&self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..*self
+        }
+    }
+}
+
+// One of the four quadrants around `origin`, in which rows go away from the origin
+// in `direction` and columns run perpendicular to it.
+struct Quadrant {
+    origin: Position,
+    direction: (isize, isize),
+    size: (usize, usize),
+}
+
+impl Quadrant {
+    const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+    // The position of the tile at `col` in row `depth`, or None if it is outside the grid
+    fn position(&self, depth: isize, col: isize) -> Option<Position> {
+        let (dx, dy) = self.direction;
+        // the column runs along the direction turned by 90 degrees
+        let x = self.origin.x.checked_add_signed(dx * depth - dy * col)?;
+        let y = self.origin.y.checked_add_signed(dy * depth + dx * col)?;
+        if x < self.size.0 && y < self.size.1 {
+            return Some((x, y).into());
+        }
+        None
+    }
+
+    // The depth and column of `pos` in this quadrant, if `pos` lies in it
+    fn depth_and_col(&self, pos: Position) -> Option<(isize, isize)> {
+        let (dx, dy) = self.direction;
+        let x = pos.x as isize - self.origin.x as isize;
+        let y = pos.y as isize - self.origin.y as isize;
+        let depth = dx * x + dy * y;
+        let col = dx * y - dy * x;
+        if depth > 0 && col.abs() <= depth {
+            return Some((depth, col));
+        }
+        None
+    }
+
+    // Symmetric shadowcasting over the rows 1 to `max_depth`,
+    // calls `reveal` with the position, depth and column of every visible tile.
+    // Tiles outside the grid block the sight.
+    fn cast<B, R>(&self, max_depth: isize, blocks: B, mut reveal: R)
+    where
+        B: Fn(Position) -> bool,
+        R: FnMut(Position, isize, isize),
+    {
+        let mut rows = vec![Row {
+            depth: 1,
+            start: Slope { num: -1, den: 1 },
+            end: Slope { num: 1, den: 1 },
+        }];
+        while let Some(mut row) = rows.pop() {
+            if row.depth > max_depth {
+                continue;
+            }
+            let mut prev_wall = None;
+            for col in row.min_col()..=row.max_col() {
+                let pos = self.position(row.depth, col);
+                let wall = pos.is_none_or(&blocks);
+                if let Some(pos) = pos {
+                    if wall || row.is_symmetric(col) {
+                        reveal(pos, row.depth, col);
+                    }
+                }
+                if prev_wall == Some(true) && !wall {
+                    row.start = Slope::of(row.depth, col);
+                }
+                if prev_wall == Some(false) && wall {
+                    let mut next = row.next();
+                    next.end = Slope::of(row.depth, col);
+                    rows.push(next);
+                }
+                prev_wall = Some(wall);
+            }
+            if prev_wall == Some(false) {
+                rows.push(row.next());
+            }
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates an iterator which yields references of every cell visible from `origin`
+    /// within `radius`, computed with symmetric shadowcasting.
+    /// Cells for which `blocks_sight` returns true are visible themselves but hide the cells behind them.
+    /// A cell is within `radius` if dx² + dy² <= radius² + radius, so a radius of 1
+    /// contains the 8 surrounding cells. The origin is always visible.
+    /// The cells are yielded from the top left to the bottom right.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, PositionsEnumerator};
+    /// let grid = Grid::from_str_map("
+    /// ...
+    /// .#.
+    /// ...
+    /// ", |c| c).unwrap();
+    /// let visible: Vec<_> = grid
+    ///     .fov((1, 2), 5, |cell, _| *cell == '#')
+    ///     .grid_positions()
+    ///     .map(|(pos, _)| pos)
+    ///     .collect();
+    /// // only the cell behind the wall is hidden
+    /// assert_eq!(visible.len(), 8);
+    /// assert!(!visible.contains(&(1, 0).into()));
+    /// ```
+    /// # Panics
+    /// * if `origin` is out of bounds.
+    pub fn fov<P, F>(&self, origin: P, radius: usize, blocks_sight: F) -> FovIter<'_, T>
+    where
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        let origin = origin.into();
+        self.check_bounds(origin)
+            .unwrap_or_else(|e| panic!("{}", e));
+
+        // no cell is further away than width + height
+        let radius = radius.min(self.width + self.height);
+        // only the cells within `radius` of the origin can be visible
+        let left = origin.x.saturating_sub(radius);
+        let top = origin.y.saturating_sub(radius);
+        let box_width = origin.x.saturating_add(radius).min(self.width - 1) - left + 1;
+        let box_height = origin.y.saturating_add(radius).min(self.height - 1) - top + 1;
+        let box_idx = |pos: Position| (pos.y - top) * box_width + pos.x - left;
+
+        let radius = radius as isize;
+        let mut visible = vec![false; box_width * box_height];
+        visible[box_idx(origin)] = true;
+        for &direction in Quadrant::DIRECTIONS.iter() {
+            let quadrant = Quadrant {
+                origin,
+                direction,
+                size: self.size(),
+            };
+            let blocks = |pos| blocks_sight(self.get_unchecked(pos), pos);
+            quadrant.cast(radius, blocks, |pos, depth, col| {
+                if depth * depth + col * col <= radius * radius + radius {
+                    visible[box_idx(pos)] = true;
+                }
+            });
+        }
+
+        let positions = visible
+            .iter()
+            .enumerate()
+            .filter(|(_, visible)| **visible)
+            .map(|(idx, _)| Position::new(left + idx % box_width, top + idx / box_width))
+            .collect();
+        FovIter {
+            positions: PositionList::new(positions),
+            grid: self.as_view(),
+        }
+    }
+
+    /// Returns if `b` can be seen from `a`, which is true if `b` is in the [Grid::fov]
+    /// of `a` with an unlimited radius. Between two cells which do not block the sight,
+    /// the line of sight is symmetric.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from_str_map("
+    /// ....
+    /// .#..
+    /// ....
+    /// ", |c| c).unwrap();
+    /// let blocks = |cell: &char, _| *cell == '#';
+    /// assert!(!grid.line_of_sight((0, 0), (2, 2), blocks));
+    /// assert!(grid.line_of_sight((0, 0), (3, 1), blocks));
+    /// ```
+    /// # Panics
+    /// * if `a` or `b` is out of bounds.
+    pub fn line_of_sight<P, F>(&self, a: P, b: P, blocks: F) -> bool
+    where
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        let (a, b) = (a.into(), b.into());
+        self.check_bounds(a).unwrap_or_else(|e| panic!("{}", e));
+        self.check_bounds(b).unwrap_or_else(|e| panic!("{}", e));
+        if a == b {
+            return true;
+        }
+
+        // b lies in one or, on a diagonal or axis, in two quadrants of a
+        Quadrant::DIRECTIONS.iter().any(|&direction| {
+            let quadrant = Quadrant {
+                origin: a,
+                direction,
+                size: self.size(),
+            };
+            let depth = match quadrant.depth_and_col(b) {
+                Some((depth, _)) => depth,
+                None => return false,
+            };
+            let mut seen = false;
+            let blocks = |pos| blocks(self.get_unchecked(pos), pos);
+            quadrant.cast(depth, blocks, |pos, _, _| seen |= pos == b);
+            seen
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PositionsEnumerator;
+
+    fn visible(grid: &Grid<char>, origin: (usize, usize), radius: usize) -> Vec<Position> {
+        grid.fov(origin, radius, |cell, _| *cell == '#')
+            .grid_positions()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    #[test]
+    fn fov_radius() {
+        let grid = Grid::new(7, 7, '.');
+        assert_eq!(visible(&grid, (3, 3), 0), vec![(3, 3).into()]);
+        assert_eq!(visible(&grid, (3, 3), 1).len(), 9);
+        // dx² + dy² <= 6 excludes the corners of the 5x5 square
+        assert_eq!(visible(&grid, (3, 3), 2).len(), 21);
+        assert_eq!(visible(&grid, (0, 0), 100).len(), 49);
+    }
+
+    #[test]
+    fn fov_pillar_shadow() {
+        let grid = Grid::from_str_map(".....\n.....\n..#..\n.....\n.....", |c| c).unwrap();
+        let positions = visible(&grid, (2, 4), 10);
+        assert_eq!(positions.len(), 23);
+        assert!(!positions.contains(&(2, 1).into()));
+        assert!(!positions.contains(&(2, 0).into()));
+        assert!(positions.contains(&(2, 2).into()));
+    }
+
+    #[test]
+    fn fov_iter_yields_cells() {
+        let grid = Grid::from_str_map("a#b", |c| c).unwrap();
+        let cells: Vec<_> = grid.fov((0, 0), 5, |cell, _| *cell == '#').collect();
+        assert_eq!(cells, vec![&'a', &'#']);
+    }
+
+    #[test]
+    fn fov_at_the_border_from_both_ends() {
+        let grid = Grid::new(7, 7, '.');
+        assert_eq!(
+            visible(&grid, (6, 0), 1),
+            vec![(5, 0).into(), (6, 0).into(), (5, 1).into(), (6, 1).into()]
+        );
+        assert_eq!(visible(&grid, (6, 6), usize::MAX).len(), 49);
+
+        let mut fov = grid.fov((0, 3), 1, |_, _| false).grid_positions();
+        assert_eq!(fov.len(), 6);
+        assert_eq!(fov.next_back(), Some(((1, 4).into(), &'.')));
+        assert_eq!(fov.next(), Some(((0, 2).into(), &'.')));
+        assert_eq!(fov.len(), 4);
+        assert_eq!(fov.rev().count(), 4);
+    }
+
+    #[test]
+    fn line_of_sight_matches_fov_and_is_symmetric() {
+        let grid = Grid::from_str_map(
+            "\
+..#.....
+.....#..
+.#......
+....##..
+......#.
+.#......",
+            |c| c,
+        )
+        .unwrap();
+        let blocks = |cell: &char, _| *cell == '#';
+        let positions: Vec<Position> = grid.positions().collect();
+        for &a in &positions {
+            let fov = visible(&grid, (a.x, a.y), 100);
+            for &b in &positions {
+                let sight = grid.line_of_sight(a, b, blocks);
+                assert_eq!(sight, fov.contains(&b), "{:?} -> {:?}", a, b);
+                if !blocks(grid.get_unchecked(a), a) && !blocks(grid.get_unchecked(b), b) {
+                    assert_eq!(
+                        sight,
+                        grid.line_of_sight(b, a, blocks),
+                        "{:?} <-> {:?}",
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,14 @@
+use super::error::GridError;
 use super::iter::*;
+use super::neighborhood::Neighborhood;
 use super::pattern::*;
-use super::step::*;
+use std::marker::PhantomData;
 use std::mem;
+use std::ops::{Index, IndexMut};
 
 /// A position in the grid.
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub x: usize,
     pub y: usize,
@@ -28,14 +32,164 @@ impl From<Position> for (usize, usize) {
     }
 }
 
+/// A rectangular region of the grid, (x, y) is the top left corner of the region.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    /// Creates a new rectangle with the top left corner at position `pos`.
+    /// # Example
+    /// ```
+    /// # use gridit::Rect;
+    /// let rect = Rect::new((1, 2), 3, 4);
+    /// assert_eq!((rect.x, rect.y, rect.width, rect.height), (1, 2, 3, 4));
+    /// ```
+    pub fn new<P: Into<Position>>(pos: P, width: usize, height: usize) -> Self {
+        let Position { x, y } = pos.into();
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Checks if position `pos` is inside the rectangle.
+    /// # Example
+    /// ```
+    /// # use gridit::Rect;
+    /// let rect = Rect::new((1, 1), 2, 2);
+    /// assert_eq!(rect.contains((2, 2)), true);
+    /// assert_eq!(rect.contains((3, 1)), false);
+    /// ```
+    pub fn contains<P: Into<Position>>(&self, pos: P) -> bool {
+        let pos = pos.into();
+        pos.x >= self.x
+            && pos.y >= self.y
+            && pos.x - self.x < self.width
+            && pos.y - self.y < self.height
+    }
+}
+
+// Returns mutable references to the cells at `positions` in the same order,
+// where `rows` yields every row of a grid from top to bottom.
+// Every position has to be in bounds and must not occur more than once.
+pub(crate) fn cells_mut<'a, T, R>(mut rows: R, positions: &[Position]) -> Vec<&'a mut T>
+where
+    R: Iterator<Item = &'a mut [T]>,
+{
+    let mut order: Vec<(usize, usize, usize)> = positions
+        .iter()
+        .enumerate()
+        .map(|(i, pos)| (pos.y, pos.x, i))
+        .collect();
+    order.sort_unstable();
+
+    let mut cells: Vec<Option<&mut T>> = positions.iter().map(|_| None).collect();
+    let mut row: Option<(usize, std::slice::IterMut<'a, T>)> = None;
+    let mut consumed_rows = 0;
+    let mut consumed_cells = 0;
+    for (y, x, i) in order {
+        let row_iter = match &mut row {
+            Some((row_y, row_iter)) if *row_y == y => row_iter,
+            _ => {
+                let next_row = rows
+                    .nth(y - consumed_rows)
+                    .expect("positions must be in bounds");
+                consumed_rows = y + 1;
+                consumed_cells = 0;
+                &mut row.insert((y, next_row.iter_mut())).1
+            }
+        };
+        let skip = x
+            .checked_sub(consumed_cells)
+            .expect("positions must not contain duplicates");
+        cells[i] = row_iter.nth(skip);
+        consumed_cells = x + 1;
+    }
+
+    cells
+        .into_iter()
+        .map(|cell| cell.expect("positions must be in bounds"))
+        .collect()
+}
+
+// Returns the number of cells of a grid with `width` and `height`,
+// or an error if a side is zero or the number does not fit into usize.
+pub(crate) fn grid_len(width: usize, height: usize) -> Result<usize, GridError> {
+    if width == 0 || height == 0 {
+        return Err(GridError::ZeroSize { width, height });
+    }
+    width
+        .checked_mul(height)
+        .ok_or(GridError::TooLarge { width, height })
+}
+
+// The length of a `width` x `height` grid grown by `columns` and `rows`,
+// the grown width and height can overflow as well.
+pub(crate) fn grown_len(
+    width: usize,
+    height: usize,
+    columns: usize,
+    rows: usize,
+) -> Result<usize, GridError> {
+    match (width.checked_add(columns), height.checked_add(rows)) {
+        (Some(width), Some(height)) => grid_len(width, height),
+        _ => Err(GridError::TooLarge {
+            width: width.saturating_add(columns),
+            height: height.saturating_add(rows),
+        }),
+    }
+}
+
 /// 2D Grid, Position (0,0) is at the top left corner
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Grid<T> {
     pub(crate) items: Vec<T>,
     pub(crate) width: usize,
     pub(crate) height: usize,
 }
 
+// Unvalidated grid data, checked by Grid::try_from_vec on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Grid")]
+struct RawGrid<T> {
+    items: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// Deserializes the grid and returns an error if width times height overflows
+/// or the number of items does not equal width times height.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Grid<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = RawGrid::deserialize(deserializer)?;
+        Grid::try_from_vec(raw.items, raw.width, raw.height).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The corner of the grid which stays in place on [Grid::resize].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Anchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
 impl<T: Clone> Grid<T> {
     /// Creates a new Grid with `default_value` as every value.
     /// # Example
@@ -50,14 +204,135 @@ impl<T: Clone> Grid<T> {
     /// # Panics
     /// * if width or height are zero
     pub fn new(width: usize, height: usize, default_value: T) -> Self {
-        if width == 0 || height == 0 {
-            panic!("width and height can not be zero");
-        }
-        Self {
+        Self::try_new(width, height, default_value).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates a new Grid with `default_value` as every value,
+    /// or returns an error if width or height are zero or width times height overflows.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, GridError};
+    /// let grid: Result<Grid<u8>, _> = Grid::try_new(0, 2, 10);
+    /// assert_eq!(grid, Err(GridError::ZeroSize { width: 0, height: 2 }));
+    /// ```
+    pub fn try_new(width: usize, height: usize, default_value: T) -> Result<Self, GridError> {
+        let len = grid_len(width, height)?;
+        Ok(Self {
             width,
             height,
-            items: vec![default_value; width * height],
+            items: vec![default_value; len],
+        })
+    }
+
+    /// Inserts a new row filled with `fill` at row `y`, shifting all rows after it down.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// grid.insert_row(1, 0);
+    /// assert_eq!(grid.size(), (2, 3));
+    /// assert_eq!(grid.row(1).collect::<Vec<_>>(), vec![&0, &0]);
+    /// assert_eq!(grid.row(2).collect::<Vec<_>>(), vec![&3, &4]);
+    /// ```
+    /// # Panics
+    /// * if `y` is greater than the height of the grid.
+    /// * if width times the new height overflows
+    pub fn insert_row(&mut self, y: usize, fill: T) {
+        assert!(y <= self.height, "row {} is out of bounds", y);
+        // checked before the items change, so the grid stays intact on a panic
+        grown_len(self.width, self.height, 0, 1).unwrap_or_else(|e| panic!("{}", e));
+        let idx = y * self.width;
+        let row = std::iter::repeat_n(fill, self.width);
+        self.items.splice(idx..idx, row);
+        self.height += 1;
+    }
+
+    /// Inserts a new column filled with `fill` at column `x`, shifting all columns after it right.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// grid.insert_column(0, 0);
+    /// assert_eq!(grid.size(), (3, 2));
+    /// assert_eq!(grid.row(0).collect::<Vec<_>>(), vec![&0, &1, &2]);
+    /// assert_eq!(grid.row(1).collect::<Vec<_>>(), vec![&0, &3, &4]);
+    /// ```
+    /// # Panics
+    /// * if `x` is greater than the width of the grid.
+    /// * if the new width times height overflows
+    pub fn insert_column(&mut self, x: usize, fill: T) {
+        assert!(x <= self.width, "column {} is out of bounds", x);
+        // checked before the items are taken, so the grid stays intact on a panic
+        let len = grown_len(self.width, self.height, 1, 0).unwrap_or_else(|e| panic!("{}", e));
+        let mut items = Vec::with_capacity(len);
+        let mut old_items = mem::take(&mut self.items).into_iter();
+        for _ in 0..self.height {
+            items.extend(old_items.by_ref().take(x));
+            items.push(fill.clone());
+            items.extend(old_items.by_ref().take(self.width - x));
+        }
+        self.items = items;
+        self.width += 1;
+    }
+
+    /// Resizes the grid to `width` and `height`.
+    /// The corner `anchor` stays in place, new cells are filled with `fill`
+    /// and cells outside the new size are dropped.
+    /// # Example
+    /// ```
+    /// # use gridit::{Anchor, Grid};
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// grid.resize(3, 1, 0, Anchor::BottomRight);
+    /// assert_eq!(grid, Grid::from(vec![0, 3, 4], 3, 1));
+    /// ```
+    /// # Panics
+    /// * if width or height are zero
+    /// * if width times height overflows
+    pub fn resize(&mut self, width: usize, height: usize, fill: T, anchor: Anchor) {
+        let len = grid_len(width, height).unwrap_or_else(|e| panic!("{}", e));
+        // Offset of the new grid in the coordinates of the old grid
+        let (anchor_right, anchor_bottom) = match anchor {
+            Anchor::TopLeft => (false, false),
+            Anchor::TopRight => (true, false),
+            Anchor::BottomLeft => (false, true),
+            Anchor::BottomRight => (true, true),
+        };
+        let offset_x = if anchor_right {
+            self.width as isize - width as isize
+        } else {
+            0
+        };
+        let offset_y = if anchor_bottom {
+            self.height as isize - height as isize
+        } else {
+            0
+        };
+
+        let old_width = self.width as isize;
+        let old_height = self.height as isize;
+        // The kept cells are taken from the old items in order
+        let mut old_items = mem::take(&mut self.items).into_iter();
+        let mut taken = 0;
+        let mut items = Vec::with_capacity(len);
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let (old_x, old_y) = (x + offset_x, y + offset_y);
+                if (0..old_width).contains(&old_x) && (0..old_height).contains(&old_y) {
+                    let idx = (old_y * old_width + old_x) as usize;
+                    let item = old_items
+                        .nth(idx - taken)
+                        .expect("every kept cell is taken once");
+                    taken = idx + 1;
+                    items.push(item);
+                } else {
+                    items.push(fill.clone());
+                }
+            }
         }
+
+        self.items = items;
+        self.width = width;
+        self.height = height;
     }
 }
 
@@ -95,15 +370,33 @@ impl<T: Default> Grid<T> {
     /// # Panics
     /// * if positions `pos` or `to` are out of bounds
     pub fn move_to<P: Into<Position>>(&mut self, pos: P, to: P) {
+        self.try_move_to(pos, to)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Moves the item at `pos` to position `to`, overrides item at `to` in the process,
+    /// and leaves the `T::Default()` in `pos`.
+    /// Returns an error and leaves the grid untouched if `pos` or `to` are out of bounds.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, GridError};
+    /// let mut grid: Grid<usize> = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.try_move_to((0, 0), (1, 1)), Ok(()));
+    /// assert_eq!(
+    ///     grid.try_move_to((0, 0), (2, 0)),
+    ///     Err(GridError::OutOfBounds { position: (2, 0).into(), width: 2, height: 2 })
+    /// );
+    /// ```
+    pub fn try_move_to<P: Into<Position>>(&mut self, pos: P, to: P) -> Result<(), GridError> {
         let pos = pos.into();
         let to = to.into();
-
-        if !self.is_bounds(pos) && !self.is_bounds(to) {
-            panic!("Out of bounds");
-        }
+        self.check_bounds(pos)?;
+        self.check_bounds(to)?;
 
         let idx_to = self.translate(to);
-        self.items[idx_to] = self.replace_default(pos).unwrap();
+        let idx_pos = self.translate(pos);
+        self.items[idx_to] = mem::take(&mut self.items[idx_pos]);
+        Ok(())
     }
 }
 
@@ -118,21 +411,38 @@ impl<T> Grid<T> {
     /// * if width or height is zero
     /// * if `v` length is not equal width times height
     pub fn from(v: Vec<T>, width: usize, height: usize) -> Self {
-        if v.len() != (width * height) {
-            panic!("v length does not equal width * height");
-        }
-        if width == 0 || height == 0 {
-            panic!("width and height can not be zero");
+        Self::try_from_vec(v, width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Constructs a new Grid with items in Vector `v`,
+    /// or returns an error if width or height is zero, width times height overflows
+    /// or `v` length is not equal width times height.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, GridError};
+    /// let grid = Grid::try_from_vec(vec![1, 2, 3, 4], 2, 2);
+    /// assert!(grid.is_ok());
+    ///
+    /// let grid = Grid::try_from_vec(vec![1, 2, 3], 2, 2);
+    /// assert_eq!(grid, Err(GridError::LengthMismatch { len: 3, width: 2, height: 2 }));
+    /// ```
+    pub fn try_from_vec(v: Vec<T>, width: usize, height: usize) -> Result<Self, GridError> {
+        if v.len() != grid_len(width, height)? {
+            return Err(GridError::LengthMismatch {
+                len: v.len(),
+                width,
+                height,
+            });
         }
-        Self {
+        Ok(Self {
             items: v,
             width,
             height,
-        }
+        })
     }
 
     #[inline]
-    fn translate<P: Into<Position>>(&self, pos: P) -> usize {
+    pub(crate) fn translate<P: Into<Position>>(&self, pos: P) -> usize {
         let pos = pos.into();
         pos.y * self.width + pos.x
     }
@@ -151,6 +461,18 @@ impl<T> Grid<T> {
         pos.x < self.width && pos.y < self.height
     }
 
+    // Returns an error if position `pos` is out of bounds.
+    pub(crate) fn check_bounds(&self, pos: Position) -> Result<(), GridError> {
+        if self.is_bounds(pos) {
+            return Ok(());
+        }
+        Err(GridError::OutOfBounds {
+            position: pos,
+            width: self.width,
+            height: self.height,
+        })
+    }
+
     /// Returns the width and height of the grid.
     /// # Example
     /// ```
@@ -169,6 +491,8 @@ impl<T> Grid<T> {
     /// let grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
     /// assert_eq!(grid.len(), 4);
     /// ```
+    // A grid always has at least one cell, so there is no is_empty
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.items.len()
     }
@@ -243,6 +567,12 @@ impl<T> Grid<T> {
         &mut self.items[idx]
     }
 
+    // Returns mutable references to the elements at `positions` in the same order.
+    // Every position has to be in bounds and must not occur more than once.
+    pub(crate) fn get_many_mut(&mut self, positions: &[Position]) -> Vec<&mut T> {
+        cells_mut(self.items.chunks_mut(self.width), positions)
+    }
+
     /// Sets the value at position `pos`.
     /// Returns None if `pos` is out of bounds,
     /// or () otherwise.
@@ -313,15 +643,32 @@ impl<T> Grid<T> {
     /// # Panics
     /// * if position `pos` is out of bounds.
     pub fn swap<P: Into<Position>>(&mut self, pos_a: P, pos_b: P) {
+        self.try_swap(pos_a, pos_b)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Swap the values of positions `pos_a` and `pos_b`,
+    /// or returns an error if one of the positions is out of bounds.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, GridError};
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.try_swap((0, 0), (1, 0)), Ok(()));
+    /// assert_eq!(
+    ///     grid.try_swap((0, 0), (0, 5)),
+    ///     Err(GridError::OutOfBounds { position: (0, 5).into(), width: 2, height: 2 })
+    /// );
+    /// ```
+    pub fn try_swap<P: Into<Position>>(&mut self, pos_a: P, pos_b: P) -> Result<(), GridError> {
         let pos_a = pos_a.into();
         let pos_b = pos_b.into();
-        if !self.is_bounds(pos_a) && !self.is_bounds(pos_b) {
-            panic!("Out of bounds");
-        }
+        self.check_bounds(pos_a)?;
+        self.check_bounds(pos_b)?;
 
         let idx_a = self.translate(pos_a);
         let idx_b = self.translate(pos_b);
         self.items.swap(idx_a, idx_b);
+        Ok(())
     }
 
     /// Move the value of position `pos` to position `to` and leaves `value` in it's place.
@@ -336,14 +683,37 @@ impl<T> Grid<T> {
     /// # Panics
     /// * if position `pos` is out of bounds
     pub fn move_and_leave<P: Into<Position>>(&mut self, pos: P, to: P, value: T) {
+        self.try_move_and_leave(pos, to, value)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Move the value of position `pos` to position `to` and leaves `value` in it's place.
+    /// Returns an error and leaves the grid untouched if `pos` or `to` are out of bounds.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, GridError};
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.try_move_and_leave((0, 0), (1, 1), 42), Ok(()));
+    /// assert_eq!(
+    ///     grid.try_move_and_leave((3, 0), (1, 1), 42),
+    ///     Err(GridError::OutOfBounds { position: (3, 0).into(), width: 2, height: 2 })
+    /// );
+    /// ```
+    pub fn try_move_and_leave<P: Into<Position>>(
+        &mut self,
+        pos: P,
+        to: P,
+        value: T,
+    ) -> Result<(), GridError> {
         let pos = pos.into();
         let to = to.into();
-        if !self.is_bounds(pos) && !self.is_bounds(to) {
-            panic!("Out of bound");
-        }
+        self.check_bounds(pos)?;
+        self.check_bounds(to)?;
 
         let idx_to = self.translate(to);
-        self.items[idx_to] = self.replace(pos, value).unwrap();
+        let idx_pos = self.translate(pos);
+        self.items[idx_to] = mem::replace(&mut self.items[idx_pos], value);
+        Ok(())
     }
 
     /// Creates an iterator which yields all positions of grid.  
@@ -360,7 +730,7 @@ impl<T> Grid<T> {
     /// ```
     pub fn positions(&self) -> PositionsIter {
         PositionsIter {
-            len: self.items.len(),
+            end: self.items.len(),
             width: self.width,
             idx: 0,
         }
@@ -382,6 +752,7 @@ impl<T> Grid<T> {
         GridIter {
             grid_iter: self.items.iter(),
             width: self.width,
+            idx: 0,
         }
     }
 
@@ -401,6 +772,7 @@ impl<T> Grid<T> {
         GridIterMut {
             grid_iter: self.items.iter_mut(),
             width: self.width,
+            idx: 0,
         }
     }
 
@@ -417,13 +789,10 @@ impl<T> Grid<T> {
     /// # Panics
     /// * if the row is out of bounds.
     pub fn row(&self, y: usize) -> RowIter<'_, T> {
-        assert!(self.is_bounds((0, y)));
-        let start_idx = y * self.width;
-        let end_idx = start_idx + self.width;
-
         RowIter {
-            row_iter: self.items[start_idx..end_idx].iter(),
+            row_iter: self.row_slice(y).iter(),
             idx: y,
+            x: 0,
         }
     }
 
@@ -440,16 +809,45 @@ impl<T> Grid<T> {
     /// # Panics
     /// * if the row is out of bounds.
     pub fn row_mut(&mut self, y: usize) -> RowIterMut<'_, T> {
-        assert!(self.is_bounds((0, y)));
-        let start_idx = y * self.width;
-        let end_idx = start_idx + self.width;
-
         RowIterMut {
-            row_iter: self.items[start_idx..end_idx].iter_mut(),
+            row_iter: self.row_slice_mut(y).iter_mut(),
             idx: y,
+            x: 0,
         }
     }
 
+    /// Returns the elements of row `y` as a slice.
+    /// Rows are stored contiguous, so this does not copy.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.row_slice(1), &[3, 4]);
+    /// ```
+    /// # Panics
+    /// * if the row is out of bounds.
+    pub fn row_slice(&self, y: usize) -> &[T] {
+        assert!(self.is_bounds((0, y)));
+        let start_idx = y * self.width;
+        &self.items[start_idx..start_idx + self.width]
+    }
+
+    /// Returns the elements of row `y` as a mutable slice.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// grid.row_slice_mut(0).copy_from_slice(&[5, 6]);
+    /// assert_eq!(grid, Grid::from(vec![5, 6, 3, 4], 2, 2));
+    /// ```
+    /// # Panics
+    /// * if the row is out of bounds.
+    pub fn row_slice_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(self.is_bounds((0, y)));
+        let start_idx = y * self.width;
+        &mut self.items[start_idx..start_idx + self.width]
+    }
+
     /// Creates an iterator which yields references of every element in column `x`.
     /// # Example
     /// ```
@@ -466,8 +864,9 @@ impl<T> Grid<T> {
         assert!(self.is_bounds((x, 0)));
         ColumnIter {
             row_idx: 0,
+            end_row: self.height,
             col_idx: x,
-            grid: &self,
+            grid: self.as_view(),
         }
     }
 
@@ -485,39 +884,14 @@ impl<T> Grid<T> {
     /// * if the column is out of bounds.
     pub fn column_mut(&mut self, x: usize) -> ColumnIterMut<'_, T> {
         assert!(self.is_bounds((x, 0)));
-        let width = self.width;
-        let iter = self.iter_mut().skip(x).step_by(width);
-        ColumnIterMut { iter, col_idx: x }
-    }
-
-    // Returns every valid neighbor position of x,y
-    fn get_neighbor_positions<P: Into<Position>>(&self, pos: P) -> Vec<Position> {
-        let Position { x, y } = pos.into();
-        let neighbor_position: [(N, N); 8] = [
-            (N::N(1), N::N(1)),
-            (N::P(0), N::N(1)),
-            (N::P(1), N::N(1)),
-            (N::N(1), N::P(0)),
-            (N::P(1), N::P(0)),
-            (N::N(1), N::P(1)),
-            (N::P(0), N::P(1)),
-            (N::P(1), N::P(1)),
-        ];
-
-        let valid_positions: Vec<Position> = neighbor_position
-            .iter()
-            .filter_map(|(nx, ny)| {
-                let x = nx.checked_add_sub(x)?;
-                let y = ny.checked_add_sub(y)?;
-
-                if self.get((x, y)).is_some() {
-                    return Some((x, y).into());
-                }
-                None
-            })
-            .collect();
-
-        valid_positions
+        let cells = self.items[x..].iter_mut().step_by(self.width);
+        ColumnIterMut {
+            cells,
+            col_idx: x,
+            row_idx: 0,
+            end_row: self.height,
+            marker: PhantomData,
+        }
     }
 
     /// Creates an iterator which yields references of every neighbor element of position `pos`.
@@ -534,12 +908,109 @@ impl<T> Grid<T> {
     /// # Panics
     /// * if x or y is out of bounds.
     pub fn neighbors<P: Into<Position>>(&self, pos: P) -> NeighborIter<'_, T> {
+        self.try_neighbors(pos).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates an iterator which yields references of every neighbor element of position `pos`,
+    /// or returns an error if `pos` is out of bounds.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, GridError};
+    /// let grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.try_neighbors((0, 1)).map(|n| n.count()), Ok(3));
+    /// assert!(grid.try_neighbors((2, 1)).is_err());
+    /// ```
+    pub fn try_neighbors<P: Into<Position>>(
+        &self,
+        pos: P,
+    ) -> Result<NeighborIter<'_, T>, GridError> {
+        let pos = pos.into();
+        self.check_bounds(pos)?;
+        Ok(self.neighbors_with(pos, &Neighborhood::moore()))
+    }
+
+    /// Creates an iterator which yields references of every element in `neighborhood` of position `pos`.
+    /// See [Neighborhood] for the available neighborhoods.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood};
+    /// let grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// let mut neighbors = grid.neighbors_with((0, 1), &Neighborhood::von_neumann());
+    /// assert_eq!(neighbors.next(), Some(&1));
+    /// assert_eq!(neighbors.next(), Some(&4));
+    /// assert_eq!(neighbors.next(), None);
+    /// ```
+    /// # Panics
+    /// * if x or y is out of bounds.
+    pub fn neighbors_with<P: Into<Position>>(
+        &self,
+        pos: P,
+        neighborhood: &Neighborhood,
+    ) -> NeighborIter<'_, T> {
+        self.as_view().neighbors_with(pos, neighborhood)
+    }
+
+    /// Creates an iterator which yields mutable references of every neighbor element of position `pos`.
+    /// Every neighbor cell is yielded exactly once.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// grid.neighbors_mut((0, 1)).for_each(|cell| *cell *= 10);
+    /// assert_eq!(grid.get((0, 0)), Some(&10));
+    /// assert_eq!(grid.get((1, 0)), Some(&20));
+    /// assert_eq!(grid.get((0, 1)), Some(&3));
+    /// assert_eq!(grid.get((1, 1)), Some(&40));
+    /// ```
+    /// # Panics
+    /// * if x or y is out of bounds.
+    pub fn neighbors_mut<P: Into<Position>>(&mut self, pos: P) -> NeighborIterMut<'_, T> {
+        self.try_neighbors_mut(pos)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates an iterator which yields mutable references of every neighbor element of position `pos`,
+    /// or returns an error if `pos` is out of bounds.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, GridError};
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.try_neighbors_mut((1, 1)).map(|n| n.count()), Ok(3));
+    /// assert!(grid.try_neighbors_mut((1, 2)).is_err());
+    /// ```
+    pub fn try_neighbors_mut<P: Into<Position>>(
+        &mut self,
+        pos: P,
+    ) -> Result<NeighborIterMut<'_, T>, GridError> {
+        let pos = pos.into();
+        self.check_bounds(pos)?;
+        Ok(self.neighbors_with_mut(pos, &Neighborhood::moore()))
+    }
+
+    /// Creates an iterator which yields mutable references of every element in `neighborhood` of position `pos`.
+    /// Every neighbor cell is yielded exactly once, even if multiple steps of `neighborhood` lead to it.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood};
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// grid.neighbors_with_mut((0, 0), &Neighborhood::von_neumann())
+    ///     .for_each(|cell| *cell = 0);
+    /// assert_eq!(grid, Grid::from(vec![1, 0, 0, 4], 2, 2));
+    /// ```
+    /// # Panics
+    /// * if x or y is out of bounds.
+    pub fn neighbors_with_mut<P: Into<Position>>(
+        &mut self,
+        pos: P,
+        neighborhood: &Neighborhood,
+    ) -> NeighborIterMut<'_, T> {
         let pos = pos.into();
         assert!(self.is_bounds(pos));
-        NeighborIter {
-            positions: self.get_neighbor_positions(pos),
-            grid: &self,
-            idx: 0,
+        let positions = self.as_view().neighbor_positions(pos, neighborhood);
+        let cells = self.get_many_mut(&positions);
+        NeighborIterMut {
+            positions: PositionList::new(positions),
+            cells: cells.into_iter(),
         }
     }
 
@@ -561,14 +1032,128 @@ impl<T> Grid<T> {
         P: Into<Position>,
         Pat: Pattern + 'static,
     {
-        let pos = pos.into();
-        PatternIter {
-            grid: &self,
-            origin_position: pos,
-            prev_position: pos,
-            pattern: Box::new(pattern),
-            repeat_count: 0,
+        self.as_view().pattern(pos, pattern)
+    }
+
+    /// Creates an iterator which yields mutable references of every element of pattern starting at position `pos`.
+    /// See [Pattern] more details.
+    ///
+    /// Patterns like [StepsPattern] can visit a cell more than once.
+    /// Every cell is only yielded the first time it is visited, later visits are skipped.
+    ///
+    /// Unlike [Grid::pattern] the pattern is walked when the iterator is created.
+    /// The walk stops once every cell of the grid was visited, but an endless pattern
+    /// which never visits every cell, e.g. a custom [Pattern] which only jumps
+    /// back and forth between two cells, never returns.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// # use gridit::pattern::StepsPattern;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// let pattern = StepsPattern::new(vec![(1,0), (-1, 0), (1, 0)]);
+    /// let mut iter = grid.pattern_mut((0, 0), pattern);
+    /// assert_eq!(iter.next(), Some(&mut 2));
+    /// assert_eq!(iter.next(), Some(&mut 1));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn pattern_mut<P, Pat>(&mut self, pos: P, pattern: Pat) -> PatternIterMut<'_, T>
+    where
+        P: Into<Position>,
+        Pat: Pattern + 'static,
+    {
+        let positions = self
+            .as_view()
+            .distinct_pattern_positions(pos.into(), pattern);
+        let cells = self.get_many_mut(&positions);
+        PatternIterMut {
+            positions: PositionList::new(positions),
+            cells: cells.into_iter(),
+        }
+    }
+
+    /// Removes row `y` and returns its items, shifting all rows after it up.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.remove_row(0), vec![1, 2]);
+    /// assert_eq!(grid, Grid::from(vec![3, 4], 2, 1));
+    /// ```
+    /// # Panics
+    /// * if the row is out of bounds.
+    /// * if the grid has only one row left.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(self.is_bounds((0, y)), "row {} is out of bounds", y);
+        assert!(self.height > 1, "can not remove the last row");
+        let start_idx = y * self.width;
+        let end_idx = start_idx + self.width;
+        self.height -= 1;
+        self.items.drain(start_idx..end_idx).collect()
+    }
+
+    /// Removes column `x` and returns its items, shifting all columns after it left.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+    /// assert_eq!(grid.remove_column(0), vec![1, 3]);
+    /// assert_eq!(grid, Grid::from(vec![2, 4], 1, 2));
+    /// ```
+    /// # Panics
+    /// * if the column is out of bounds.
+    /// * if the grid has only one column left.
+    pub fn remove_column(&mut self, x: usize) -> Vec<T> {
+        assert!(self.is_bounds((x, 0)), "column {} is out of bounds", x);
+        assert!(self.width > 1, "can not remove the last column");
+        let mut items = Vec::with_capacity((self.width - 1) * self.height);
+        let mut column = Vec::with_capacity(self.height);
+        let mut old_items = mem::take(&mut self.items).into_iter();
+        for _ in 0..self.height {
+            items.extend(old_items.by_ref().take(x));
+            column.extend(old_items.next());
+            items.extend(old_items.by_ref().take(self.width - x - 1));
         }
+        self.items = items;
+        self.width -= 1;
+        column
+    }
+}
+
+/// Returns a reference to the element at position `pos`.
+/// # Example
+/// ```
+/// # use gridit::{Grid, Position};
+/// let grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+/// assert_eq!(grid[(1, 0)], 2);
+/// assert_eq!(grid[Position::new(0, 1)], 3);
+/// ```
+/// # Panics
+/// * if `pos` is out of bounds.
+impl<T, P: Into<Position>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        self.check_bounds(pos).unwrap_or_else(|e| panic!("{}", e));
+        self.get_unchecked(pos)
+    }
+}
+
+/// Returns a mutable reference to the element at position `pos`.
+/// # Example
+/// ```
+/// # use gridit::Grid;
+/// let mut grid = Grid::from(vec![1, 2, 3, 4], 2, 2);
+/// grid[(1, 1)] = 10;
+/// assert_eq!(grid, Grid::from(vec![1, 2, 3, 10], 2, 2));
+/// ```
+/// # Panics
+/// * if `pos` is out of bounds.
+impl<T, P: Into<Position>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        let pos = pos.into();
+        self.check_bounds(pos).unwrap_or_else(|e| panic!("{}", e));
+        self.get_mut_unchecked(pos)
     }
 }
 
@@ -709,4 +1294,270 @@ mod tests {
         assert_eq!(grid.get((1, 0)), Some(&10));
         assert_eq!(grid.get((0, 0)), Some(&1));
     }
+
+    #[test]
+    fn try_from_vec() {
+        assert_eq!(
+            Grid::try_from_vec(vec![1, 2, 3], 2, 2),
+            Err(GridError::LengthMismatch {
+                len: 3,
+                width: 2,
+                height: 2
+            })
+        );
+        assert_eq!(
+            Grid::<u8>::try_from_vec(vec![], 0, 2),
+            Err(GridError::ZeroSize {
+                width: 0,
+                height: 2
+            })
+        );
+        assert_eq!(
+            Grid::try_from_vec(vec![1, 2], 1, 2),
+            Ok(Grid {
+                items: vec![1, 2],
+                width: 1,
+                height: 2
+            })
+        );
+    }
+
+    #[test]
+    fn too_large_size() {
+        let too_large = GridError::TooLarge {
+            width: usize::MAX,
+            height: usize::MAX,
+        };
+        assert_eq!(
+            Grid::<u8>::try_from_vec(vec![], usize::MAX, usize::MAX),
+            Err(too_large)
+        );
+        assert_eq!(Grid::try_new(usize::MAX, usize::MAX, 0), Err(too_large));
+        assert_eq!(
+            Grid::try_new(usize::MAX, 2, 0),
+            Err(GridError::TooLarge {
+                width: usize::MAX,
+                height: 2
+            })
+        );
+    }
+
+    #[test]
+    fn insert_too_large() {
+        let half = usize::MAX / 2 + 1;
+        let mut grid = Grid::new(2, half - 2, ());
+        grid.insert_row(0, ());
+        assert_eq!(grid.size(), (2, half - 1));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            grid.insert_row(0, ());
+        }));
+        assert!(result.is_err());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            grid.insert_column(0, ());
+        }));
+        assert!(result.is_err());
+        // the grid is left intact
+        assert_eq!(grid.size(), (2, half - 1));
+        assert_eq!(grid.len(), usize::MAX - 1);
+
+        let mut grid = Grid::new(1, usize::MAX, ());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            grid.insert_row(0, ());
+        }));
+        assert!(result.is_err());
+        assert_eq!(grid.size(), (1, usize::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit into usize")]
+    fn resize_too_large() {
+        let mut grid = Grid::new(2, 2, 0);
+        grid.resize(usize::MAX, usize::MAX, 0, Anchor::TopLeft);
+    }
+
+    #[test]
+    fn try_swap_out_of_bounds() {
+        let mut grid = Grid {
+            items: (0..6).collect(),
+            width: 2,
+            height: 3,
+        };
+
+        let err = grid.try_swap((1, 2), (2, 1));
+        assert_eq!(
+            err,
+            Err(GridError::OutOfBounds {
+                position: (2, 1).into(),
+                width: 2,
+                height: 3
+            })
+        );
+        assert_eq!(grid.items, (0..6).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_one_position_out_of_bounds() {
+        let mut grid = Grid {
+            items: (0..6).collect(),
+            width: 2,
+            height: 3,
+        };
+        grid.swap((0, 0), (2, 0));
+    }
+
+    #[test]
+    fn try_move_to_out_of_bounds() {
+        let mut grid = Grid {
+            items: (0..4).collect(),
+            width: 2,
+            height: 2,
+        };
+
+        assert!(grid.try_move_to((0, 2), (0, 1)).is_err());
+        assert!(grid.try_move_to((0, 1), (0, 2)).is_err());
+        assert!(grid.try_move_and_leave((5, 0), (0, 0), 9).is_err());
+        assert_eq!(grid.items, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn grid_error_display() {
+        let err = GridError::OutOfBounds {
+            position: (4, 1).into(),
+            width: 3,
+            height: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            "position (4, 1) is out of bounds of 3x2 grid"
+        );
+    }
+
+    #[test]
+    fn insert_and_remove_rows() {
+        let mut grid = Grid {
+            items: (0..6).collect(),
+            width: 3,
+            height: 2,
+        };
+
+        grid.insert_row(2, 9);
+        grid.insert_row(0, 8);
+        assert_eq!(grid.size(), (3, 4));
+        assert_eq!(grid.items, vec![8, 8, 8, 0, 1, 2, 3, 4, 5, 9, 9, 9]);
+
+        assert_eq!(grid.remove_row(2), vec![3, 4, 5]);
+        assert_eq!(grid.size(), (3, 3));
+        assert_eq!(grid.items, vec![8, 8, 8, 0, 1, 2, 9, 9, 9]);
+    }
+
+    #[test]
+    fn insert_and_remove_columns() {
+        let mut grid = Grid {
+            items: (0..6).collect(),
+            width: 3,
+            height: 2,
+        };
+
+        grid.insert_column(3, 9);
+        grid.insert_column(1, 8);
+        assert_eq!(grid.size(), (5, 2));
+        assert_eq!(grid.items, vec![0, 8, 1, 2, 9, 3, 8, 4, 5, 9]);
+        assert_eq!(grid.column(4).collect::<Vec<_>>(), vec![&9, &9]);
+
+        assert_eq!(grid.remove_column(0), vec![0, 3]);
+        assert_eq!(grid.size(), (4, 2));
+        assert_eq!(grid.items, vec![8, 1, 2, 9, 8, 4, 5, 9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_last_row() {
+        let mut grid = Grid::new(3, 1, 0);
+        grid.remove_row(0);
+    }
+
+    #[test]
+    fn resize_anchors() {
+        // 0, 1, 2
+        // 3, 4, 5
+        let grid = Grid {
+            items: (0..6).collect(),
+            width: 3,
+            height: 2,
+        };
+
+        let mut top_left = Grid::from(grid.items.clone(), 3, 2);
+        top_left.resize(2, 3, 9, Anchor::TopLeft);
+        assert_eq!(top_left.items, vec![0, 1, 3, 4, 9, 9]);
+
+        let mut top_right = Grid::from(grid.items.clone(), 3, 2);
+        top_right.resize(2, 3, 9, Anchor::TopRight);
+        assert_eq!(top_right.items, vec![1, 2, 4, 5, 9, 9]);
+
+        let mut bottom_left = Grid::from(grid.items.clone(), 3, 2);
+        bottom_left.resize(4, 1, 9, Anchor::BottomLeft);
+        assert_eq!(bottom_left.items, vec![3, 4, 5, 9]);
+
+        let mut bottom_right = Grid::from(grid.items.clone(), 3, 2);
+        bottom_right.resize(4, 3, 9, Anchor::BottomRight);
+        assert_eq!(bottom_right.size(), (4, 3));
+        assert_eq!(bottom_right.items, vec![9, 9, 9, 9, 9, 0, 1, 2, 9, 3, 4, 5]);
+        assert_eq!(bottom_right.neighbors((0, 0)).count(), 3);
+    }
+
+    #[test]
+    fn index_and_row_slices() {
+        let mut grid = Grid {
+            width: 3,
+            height: 2,
+            items: (0..6).collect(),
+        };
+        assert_eq!(grid[(2, 1)], 5);
+        grid[Position::new(0, 1)] = 30;
+        assert_eq!(grid.row_slice(1), &[30, 4, 5]);
+        grid.row_slice_mut(0).reverse();
+        assert_eq!(grid.items, vec![2, 1, 0, 30, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "position (3, 0) is out of bounds of 3x2 grid")]
+    fn index_out_of_bounds() {
+        let grid = Grid::new(3, 2, 0);
+        let _ = grid[(3, 0)];
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let grid = Grid {
+            width: 2,
+            height: 2,
+            items: vec![1, 2, 3, 4],
+        };
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, r#"{"items":[1,2,3,4],"width":2,"height":2}"#);
+        assert_eq!(serde_json::from_str::<Grid<i32>>(&json).unwrap(), grid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_invalid_length() {
+        let json = r#"{"items":[1,2,3],"width":2,"height":2}"#;
+        let err = serde_json::from_str::<Grid<i32>>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("length 3 does not equal width * height of 2x2"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_too_large_size() {
+        let json = r#"{"items":[],"width":4294967296,"height":4294967296}"#;
+        let err = serde_json::from_str::<Grid<i32>>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("width * height of 4294967296x4294967296 does not fit into usize"));
+    }
 }
@@ -1,63 +1,161 @@
 use super::{Positions, PositionsEnumerator};
-use crate::iter::GridIterMut;
-use crate::Grid;
-use std::iter::{Skip, StepBy};
+use crate::view::GridView;
+use std::iter::{FusedIterator, StepBy};
+use std::marker::PhantomData;
+use std::slice::IterMut;
 
 pub struct ColumnIter<'a, T> {
+    // row of the next cell from the front
     pub(crate) row_idx: usize,
+    // row after the next cell from the back
+    pub(crate) end_row: usize,
     pub(crate) col_idx: usize,
-    pub(crate) grid: &'a Grid<T>,
+    pub(crate) grid: GridView<'a, T>,
 }
 
 impl<'a, T> Iterator for ColumnIter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.row_idx >= self.end_row {
+            return None;
+        }
         self.row_idx += 1;
-        self.grid.get((self.col_idx, self.row_idx - 1))
+        Some(
+            self.grid
+                .get_unchecked((self.col_idx, self.row_idx - 1).into()),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end_row - self.row_idx;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ColumnIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.row_idx >= self.end_row {
+            return None;
+        }
+        self.end_row -= 1;
+        Some(self.grid.get_unchecked((self.col_idx, self.end_row).into()))
     }
 }
 
+impl<'a, T> ExactSizeIterator for ColumnIter<'a, T> {}
+
+impl<'a, T> FusedIterator for ColumnIter<'a, T> {}
+
 impl<'a, T: 'static> PositionsEnumerator for ColumnIter<'a, T> {
     fn grid_positions(self) -> Positions<Self> {
         Positions {
+            last_pos: |inner| (inner.col_idx, inner.row_idx - 1).into(),
+            last_back_pos: |inner| (inner.col_idx, inner.end_row).into(),
             inner: self,
-            prev_position: None,
-            next_pos: |inner, _| (inner.col_idx, inner.row_idx).into(),
         }
     }
 }
 
-pub struct ColumnIterMut<'a, T> {
-    // TODO change column_iter to be a more generic type that implements Iterator
-    pub(crate) iter: StepBy<Skip<GridIterMut<'a, T>>>,
+/// Iterator over mutable references of the cells of a column, e.g. created by [Grid::column_mut](crate::Grid::column_mut).
+/// `C` yields the cells from the top row to the bottom row.
+pub struct ColumnIterMut<'a, T, C = StepBy<IterMut<'a, T>>> {
+    pub(crate) cells: C,
     pub(crate) col_idx: usize,
+    // row of the next cell from the front
+    pub(crate) row_idx: usize,
+    // row after the next cell from the back
+    pub(crate) end_row: usize,
+    pub(crate) marker: PhantomData<&'a mut T>,
 }
 
-impl<'a, T> Iterator for ColumnIterMut<'a, T> {
+impl<'a, T, C> Iterator for ColumnIterMut<'a, T, C>
+where
+    C: DoubleEndedIterator<Item = &'a mut T>,
+{
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        if self.row_idx >= self.end_row {
+            return None;
+        }
+        self.row_idx += 1;
+        self.cells.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end_row - self.row_idx;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, C> DoubleEndedIterator for ColumnIterMut<'a, T, C>
+where
+    C: DoubleEndedIterator<Item = &'a mut T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.row_idx >= self.end_row {
+            return None;
+        }
+        self.end_row -= 1;
+        self.cells.next_back()
     }
 }
 
-impl<'a, T: 'static> PositionsEnumerator for ColumnIterMut<'a, T> {
+impl<'a, T, C> ExactSizeIterator for ColumnIterMut<'a, T, C> where
+    C: DoubleEndedIterator<Item = &'a mut T>
+{
+}
+
+impl<'a, T, C> FusedIterator for ColumnIterMut<'a, T, C> where
+    C: DoubleEndedIterator<Item = &'a mut T>
+{
+}
+
+impl<'a, T: 'static, C> PositionsEnumerator for ColumnIterMut<'a, T, C>
+where
+    C: DoubleEndedIterator<Item = &'a mut T>,
+{
     fn grid_positions(self) -> Positions<Self> {
         Positions {
+            last_pos: |inner| (inner.col_idx, inner.row_idx - 1).into(),
+            last_back_pos: |inner| (inner.col_idx, inner.end_row).into(),
             inner: self,
-            prev_position: None,
-            next_pos: |inner, prev_pos| match prev_pos {
-                None => (inner.col_idx, 0).into(),
-                Some(p) => (p.x, p.y + 1).into(),
-            },
         }
     }
 }
 
+/// The cells of a column of a [GridViewMut](crate::GridViewMut), one cell of every row of the view.
+/// Used by [GridViewMut::column_mut](crate::GridViewMut::column_mut).
+pub struct ViewColumnCellsMut<'a, 'r, T> {
+    pub(crate) rows: IterMut<'a, &'r mut [T]>,
+    pub(crate) col_idx: usize,
+}
+
+impl<'a, 'r, T> Iterator for ViewColumnCellsMut<'a, 'r, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+        Some(&mut row[self.col_idx])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<'a, 'r, T> DoubleEndedIterator for ViewColumnCellsMut<'a, 'r, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next_back()?;
+        Some(&mut row[self.col_idx])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Grid;
 
     #[test]
     fn column_iter() {
@@ -134,4 +232,45 @@ mod tests {
         assert_eq!(col_pos.next(), Some(((1, 1).into(), &mut 1)));
         assert_eq!(col_pos.next(), None);
     }
+
+    #[test]
+    fn column_iter_stays_exhausted() {
+        let grid = Grid {
+            width: 2,
+            height: 2,
+            items: vec![0, 1, 0, 1],
+        };
+
+        let mut col_iter = grid.column(0);
+        assert_eq!(col_iter.len(), 2);
+        col_iter.by_ref().for_each(drop);
+        for _ in 0..3 {
+            assert_eq!(col_iter.next(), None);
+            assert_eq!(col_iter.next_back(), None);
+        }
+        assert_eq!(col_iter.len(), 0);
+    }
+
+    #[test]
+    fn column_iter_double_ended_positions() {
+        let mut grid = Grid {
+            width: 2,
+            height: 3,
+            items: (0..6).collect(),
+        };
+
+        let mut col_pos = grid.column(1).grid_positions();
+        assert_eq!(col_pos.next_back(), Some(((1, 2).into(), &5)));
+        assert_eq!(col_pos.next(), Some(((1, 0).into(), &1)));
+        assert_eq!(col_pos.len(), 1);
+        assert_eq!(col_pos.next_back(), Some(((1, 1).into(), &3)));
+        assert_eq!(col_pos.next(), None);
+        assert_eq!(col_pos.next_back(), None);
+
+        let mut col_pos = grid.column_mut(0).grid_positions().rev();
+        assert_eq!(col_pos.next(), Some(((0, 2).into(), &mut 4)));
+        assert_eq!(col_pos.next(), Some(((0, 1).into(), &mut 2)));
+        assert_eq!(col_pos.next(), Some(((0, 0).into(), &mut 0)));
+        assert_eq!(col_pos.next(), None);
+    }
 }
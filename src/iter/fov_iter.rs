@@ -0,0 +1,43 @@
+use super::{PositionList, Positions, PositionsEnumerator};
+use crate::view::GridView;
+use std::iter::FusedIterator;
+
+/// Iterator over the visible cells of a field of view, created by [Grid::fov](crate::Grid::fov).
+pub struct FovIter<'a, T> {
+    pub(crate) positions: PositionList,
+    pub(crate) grid: GridView<'a, T>,
+}
+
+impl<'a, T> Iterator for FovIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next()?;
+        Some(self.grid.get_unchecked(pos))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for FovIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next_back()?;
+        Some(self.grid.get_unchecked(pos))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for FovIter<'a, T> {}
+
+impl<'a, T> FusedIterator for FovIter<'a, T> {}
+
+impl<'a, T> PositionsEnumerator for FovIter<'a, T> {
+    fn grid_positions(self) -> Positions<Self> {
+        Positions {
+            last_pos: |inner| inner.positions.last_front(),
+            last_back_pos: |inner| inner.positions.last_back(),
+            inner: self,
+        }
+    }
+}
@@ -1,65 +1,87 @@
 use super::{Positions, PositionsEnumerator};
+use crate::grid::Position;
+use std::iter::FusedIterator;
 
 pub struct GridIter<'a, T> {
     pub(crate) grid_iter: std::slice::Iter<'a, T>,
     pub(crate) width: usize,
+    // index of the next cell from the front
+    pub(crate) idx: usize,
 }
 
 impl<'a, T> Iterator for GridIter<'a, T> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
-        self.grid_iter.next()
+        let cell = self.grid_iter.next()?;
+        self.idx += 1;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.grid_iter.size_hint()
     }
 }
 
+impl<'a, T> DoubleEndedIterator for GridIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.grid_iter.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for GridIter<'a, T> {}
+
+impl<'a, T> FusedIterator for GridIter<'a, T> {}
+
 impl<'a, T: 'static> PositionsEnumerator for GridIter<'a, T> {
     fn grid_positions(self) -> Positions<GridIter<'a, T>> {
         Positions {
+            last_pos: |inner| index_position(inner.idx - 1, inner.width),
+            last_back_pos: |inner| index_position(inner.idx + inner.len(), inner.width),
             inner: self,
-            prev_position: None,
-            next_pos: |inner, prev_pos| {
-                let (px, py) = match prev_pos {
-                    Some(xy) => xy.into(),
-                    None => return (0, 0).into(),
-                };
-                let (x, y) = match px == (inner.width - 1) {
-                    true => (0, py + 1),   // go one row down and reset x to 0
-                    false => (px + 1, py), // keep moving x forward
-                };
-                (x, y).into()
-            },
         }
     }
 }
 
+fn index_position(idx: usize, width: usize) -> Position {
+    (idx % width, idx / width).into()
+}
+
 pub struct GridIterMut<'a, T> {
     pub(crate) grid_iter: std::slice::IterMut<'a, T>,
     pub(crate) width: usize,
+    // index of the next cell from the front
+    pub(crate) idx: usize,
 }
 
 impl<'a, T> Iterator for GridIterMut<'a, T> {
     type Item = &'a mut T;
     fn next(&mut self) -> Option<Self::Item> {
-        self.grid_iter.next()
+        let cell = self.grid_iter.next()?;
+        self.idx += 1;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.grid_iter.size_hint()
     }
 }
 
+impl<'a, T> DoubleEndedIterator for GridIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.grid_iter.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for GridIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for GridIterMut<'a, T> {}
+
 impl<'a, T: 'static> PositionsEnumerator for GridIterMut<'a, T> {
     fn grid_positions(self) -> Positions<Self> {
         Positions {
+            last_pos: |inner| index_position(inner.idx - 1, inner.width),
+            last_back_pos: |inner| index_position(inner.idx + inner.len(), inner.width),
             inner: self,
-            prev_position: None,
-            next_pos: |inner, prev_pos| {
-                let (px, py) = match prev_pos {
-                    Some(xy) => xy.into(),
-                    None => return (0, 0).into(),
-                };
-                let (x, y) = match px == (inner.width - 1) {
-                    true => (0, py + 1),   // go one row down and reset x to 0
-                    false => (px + 1, py), // keep moving x forward
-                };
-                (x, y).into()
-            },
         }
     }
 }
@@ -121,4 +143,27 @@ mod tests {
         assert_eq!(iter.next(), Some(((1, 1).into(), &mut 9)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn grid_iter_rev_positions() {
+        let mut grid = Grid {
+            width: 2,
+            height: 2,
+            items: (0..4).collect(),
+        };
+        assert_eq!(grid.iter().len(), 4);
+        assert_eq!(grid.iter().rev().collect::<Vec<_>>(), vec![&3, &2, &1, &0]);
+
+        let mut iter = grid.iter().grid_positions();
+        assert_eq!(iter.next_back(), Some(((1, 1).into(), &3)));
+        assert_eq!(iter.next_back(), Some(((0, 1).into(), &2)));
+        assert_eq!(iter.next(), Some(((0, 0).into(), &0)));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(((1, 0).into(), &1)));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = grid.iter_mut().grid_positions().rev();
+        assert_eq!(iter.next(), Some(((1, 1).into(), &mut 3)));
+        assert_eq!(iter.next(), Some(((0, 1).into(), &mut 2)));
+    }
 }
@@ -1,17 +1,23 @@
 //! All iterators used in [Grid](crate::Grid).
 
 mod column_iter;
+mod fov_iter;
 mod grid_iter;
 mod neighbor_iter;
 mod pattern_iter;
 mod position_enumerator;
 mod position_iter;
+mod position_list;
 mod row_iter;
+mod shape_iter;
 
-pub use column_iter::{ColumnIter, ColumnIterMut};
+pub use column_iter::{ColumnIter, ColumnIterMut, ViewColumnCellsMut};
+pub use fov_iter::FovIter;
 pub use grid_iter::{GridIter, GridIterMut};
-pub use neighbor_iter::NeighborIter;
-pub use pattern_iter::PatternIter;
+pub use neighbor_iter::{NeighborIter, NeighborIterMut};
+pub use pattern_iter::{PatternIter, PatternIterMut};
 pub use position_enumerator::{Positions, PositionsEnumerator};
 pub use position_iter::PositionsIter;
+pub(crate) use position_list::PositionList;
 pub use row_iter::{RowIter, RowIterMut};
+pub use shape_iter::{ShapeIter, ShapeIterMut};
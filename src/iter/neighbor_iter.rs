@@ -1,62 +1,89 @@
-use super::{Positions, PositionsEnumerator};
-use crate::grid::{Grid, Position};
+use super::{PositionList, Positions, PositionsEnumerator};
+use crate::view::GridView;
+use std::iter::FusedIterator;
 
 pub struct NeighborIter<'a, T> {
-    pub(crate) positions: Vec<Position>,
-    pub(crate) grid: &'a Grid<T>,
-    pub(crate) idx: usize,
+    pub(crate) positions: PositionList,
+    pub(crate) grid: GridView<'a, T>,
 }
 
 impl<'a, T> Iterator for NeighborIter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.idx >= self.positions.len() {
-            return None;
-        }
-        let pos = self.positions[self.idx];
-        self.idx += 1;
-        let cell = self.grid.get_unchecked(pos);
-        Some(cell)
+        let pos = self.positions.next()?;
+        Some(self.grid.get_unchecked(pos))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
     }
 }
 
+impl<'a, T> DoubleEndedIterator for NeighborIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next_back()?;
+        Some(self.grid.get_unchecked(pos))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for NeighborIter<'a, T> {}
+
+impl<'a, T> FusedIterator for NeighborIter<'a, T> {}
+
 impl<'a, T> PositionsEnumerator for NeighborIter<'a, T> {
     fn grid_positions(self) -> Positions<Self> {
         Positions {
+            last_pos: |inner| inner.positions.last_front(),
+            last_back_pos: |inner| inner.positions.last_back(),
             inner: self,
-            next_pos: |inner, _| {
-                if inner.idx < inner.positions.len() {
-                    inner.positions[inner.idx]
-                } else {
-                    // This only happens when inner.next() returns None
-                    // But we need to check since idx can be out of bounds.
-                    (0, 0).into()
-                }
-            },
-            prev_position: None,
         }
     }
 }
 
-// pub struct NeighborIterMut<'a,T> {
-//     pub(crate) positions: Box<Iterator<Item = (usize, usize)>>,
-//     pub(crate) grid: &'a mut Grid<T>,
-// }
+pub struct NeighborIterMut<'a, T> {
+    pub(crate) positions: PositionList,
+    pub(crate) cells: std::vec::IntoIter<&'a mut T>,
+}
 
-// impl<'a, T> Iterator for NeighborIterMut<'a, T> {
-//     type Item = &'a mut T;
+impl<'a, T> Iterator for NeighborIterMut<'a, T> {
+    type Item = &'a mut T;
 
-//     fn next(&mut self) -> Option<Self::Item> {
-//         let pos = self.positions.next()?;
-//         let mut cell = self.grid.get_mut_unchecked(pos.0, pos.1);
-//         Some(cell)
-//     }
-// }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.positions.next()?;
+        self.cells.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for NeighborIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.positions.next_back()?;
+        self.cells.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for NeighborIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for NeighborIterMut<'a, T> {}
+
+impl<'a, T> PositionsEnumerator for NeighborIterMut<'a, T> {
+    fn grid_positions(self) -> Positions<Self> {
+        Positions {
+            last_pos: |inner| inner.positions.last_front(),
+            last_back_pos: |inner| inner.positions.last_back(),
+            inner: self,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Grid;
 
     #[test]
     fn neighbor_iter() {
@@ -132,4 +159,66 @@ mod tests {
         assert_eq!(neighbor_pos.next(), Some(((0, 1).into(), &2)));
         assert_eq!(neighbor_pos.next(), None);
     }
+
+    #[test]
+    fn neighbor_iter_mut() {
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+
+        grid.neighbors_mut((0, 0)).for_each(|cell| *cell += 10);
+        assert_eq!(grid.items, vec![0, 11, 2, 13, 14, 5, 6, 7, 8]);
+
+        let mut neighbors = grid.neighbors_mut((2, 1));
+        assert_eq!(neighbors.next(), Some(&mut 11));
+        assert_eq!(neighbors.next(), Some(&mut 2));
+        assert_eq!(neighbors.next(), Some(&mut 14));
+        assert_eq!(neighbors.next(), Some(&mut 7));
+        assert_eq!(neighbors.next(), Some(&mut 8));
+        assert_eq!(neighbors.next(), None);
+    }
+
+    #[test]
+    fn neighbor_iter_mut_positions() {
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+
+        let mut neighbor_pos = grid.neighbors_mut((1, 0)).grid_positions();
+        assert_eq!(neighbor_pos.next(), Some(((0, 0).into(), &mut 0)));
+        assert_eq!(neighbor_pos.next(), Some(((2, 0).into(), &mut 2)));
+        assert_eq!(neighbor_pos.next(), Some(((0, 1).into(), &mut 3)));
+        assert_eq!(neighbor_pos.next(), Some(((1, 1).into(), &mut 4)));
+        assert_eq!(neighbor_pos.next(), Some(((2, 1).into(), &mut 5)));
+        assert_eq!(neighbor_pos.next(), None);
+    }
+
+    #[test]
+    fn neighbor_iter_double_ended_positions() {
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+
+        let mut neighbor_pos = grid.neighbors((0, 0)).grid_positions();
+        assert_eq!(neighbor_pos.len(), 3);
+        assert_eq!(neighbor_pos.next_back(), Some(((1, 1).into(), &4)));
+        assert_eq!(neighbor_pos.next(), Some(((1, 0).into(), &1)));
+        assert_eq!(neighbor_pos.next_back(), Some(((0, 1).into(), &3)));
+        assert_eq!(neighbor_pos.next(), None);
+        assert_eq!(neighbor_pos.next_back(), None);
+
+        let positions: Vec<_> = grid
+            .neighbors_mut((2, 2))
+            .grid_positions()
+            .rev()
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(positions, vec![(1, 2).into(), (2, 1).into(), (1, 1).into()]);
+    }
 }
@@ -1,16 +1,135 @@
-use super::{Positions, PositionsEnumerator};
+use super::{PositionList, Positions, PositionsEnumerator};
 use crate::pattern::{Action, Pattern, Repeat};
-use crate::{Grid, Position};
+use crate::view::GridView;
+use crate::{Position, Step};
+use std::collections::HashSet;
+use std::iter::FusedIterator;
 
 pub struct PatternIter<'a, T> {
-    pub(crate) grid: &'a Grid<T>,
+    pub(crate) grid: GridView<'a, T>,
     pub(crate) prev_position: Position,
     pub(crate) pattern: Box<dyn Pattern>,
     pub(crate) repeat_count: usize,
     pub(crate) origin_position: Position,
+    // The first step and if every action so far was this step,
+    // used to end straight rays on wrapping grids.
+    pub(crate) ray_step: Option<Step>,
+    pub(crate) is_ray: bool,
+    // The yielded positions, if the pattern is distinct
+    pub(crate) visited: HashSet<Position>,
+    pub(crate) blocking: Option<Blocking<'a, T>>,
+    // If the last cell blocked the current segment
+    pub(crate) blocked: bool,
+}
+
+type BlocksFn<'a, T> = Box<dyn Fn(&T, Position) -> bool + 'a>;
+
+pub(crate) struct Blocking<'a, T> {
+    blocks: BlocksFn<'a, T>,
+    inclusive: bool,
 }
 
 impl<'a, T> PatternIter<'a, T> {
+    pub(crate) fn new(grid: GridView<'a, T>, origin: Position, pattern: Box<dyn Pattern>) -> Self {
+        Self {
+            grid,
+            prev_position: origin,
+            pattern,
+            repeat_count: 0,
+            origin_position: origin,
+            ray_step: None,
+            is_ray: true,
+            visited: HashSet::new(),
+            blocking: None,
+            blocked: false,
+        }
+    }
+
+    /// Ends every walk of the pattern before the first cell for which `blocks` returns true,
+    /// e.g. a sliding piece which can not move onto or over other pieces.
+    /// The next segment of the pattern starts again at the original position,
+    /// so every ray of a combined pattern is blocked on its own.
+    ///
+    /// Only cells reached with `Action::Step` end a walk. Blocking cells reached with
+    /// `Action::StepFromOrigin` or `Action::Jump` are skipped and the pattern goes on.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+    /// let grid = Grid::from_str_map("
+    /// .x..
+    /// ....
+    /// x...
+    /// ", |c| c).unwrap();
+    /// let rook = DirectionPattern::new((1, 0), Repeat::TillEnd).rotations4();
+    /// let moves = grid.pattern((0, 0), rook).until_blocked(|cell, _| *cell == 'x');
+    /// assert_eq!(moves.count(), 1);
+    /// ```
+    pub fn until_blocked<F>(self, blocks: F) -> Self
+    where
+        F: Fn(&T, Position) -> bool + 'a,
+    {
+        self.with_blocking(blocks, false)
+    }
+
+    /// Ends every walk of the pattern at the first cell for which `blocks` returns true,
+    /// the blocking cell is yielded. E.g. a sliding piece which can capture the first
+    /// piece in its way. See [PatternIter::until_blocked].
+    ///
+    /// Blocking cells reached with `Action::StepFromOrigin` or `Action::Jump` are yielded
+    /// and the pattern goes on.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+    /// use gridit::PositionsEnumerator;
+    /// let grid = Grid::from_str_map("
+    /// .x..
+    /// ....
+    /// x...
+    /// ", |c| c).unwrap();
+    /// let rook = DirectionPattern::new((1, 0), Repeat::TillEnd).rotations4();
+    /// let moves: Vec<_> = grid
+    ///     .pattern((0, 0), rook)
+    ///     .until_blocked_inclusive(|cell, _| *cell == 'x')
+    ///     .grid_positions()
+    ///     .map(|(pos, _)| pos)
+    ///     .collect();
+    /// assert_eq!(moves, vec![(1, 0).into(), (0, 1).into(), (0, 2).into()]);
+    /// ```
+    pub fn until_blocked_inclusive<F>(self, blocks: F) -> Self
+    where
+        F: Fn(&T, Position) -> bool + 'a,
+    {
+        self.with_blocking(blocks, true)
+    }
+
+    fn with_blocking<F>(mut self, blocks: F, inclusive: bool) -> Self
+    where
+        F: Fn(&T, Position) -> bool + 'a,
+    {
+        self.blocking = Some(Blocking {
+            blocks: Box::new(blocks),
+            inclusive,
+        });
+        self
+    }
+
+    // Ends the current segment, the next segment starts again at the origin position.
+    fn segment_done(&mut self) -> Option<()> {
+        if !self.pattern.next_segment() {
+            return None;
+        }
+        if self.pattern.new_group() {
+            self.visited.clear();
+        }
+        self.prev_position = self.origin_position;
+        self.repeat_count = 0;
+        self.ray_step = None;
+        self.is_ray = true;
+        Some(())
+    }
+
     fn repeation_done(&self) -> Option<()> {
         match self.pattern.repeat() {
             Repeat::Once if self.repeat_count != 0 => None,
@@ -18,58 +137,93 @@ impl<'a, T> PatternIter<'a, T> {
             _ => Some(()),
         }
     }
+
+    // On a wrapping grid a straight ray with Repeat::TillEnd never reaches the end,
+    // it ends before it returns to the origin position.
+    fn ray_done(&mut self, step: Step, next_position: Position) -> Option<()> {
+        match self.ray_step {
+            None => self.ray_step = Some(step),
+            Some(ray_step) if ray_step != step => self.is_ray = false,
+            _ => (),
+        }
+        if self.grid.wraps()
+            && self.is_ray
+            && matches!(self.pattern.repeat(), Repeat::TillEnd)
+            && next_position == self.origin_position
+        {
+            return None;
+        }
+        Some(())
+    }
 }
 
 impl<'a, T> Iterator for PatternIter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.repeation_done()?;
-        let action = self.pattern.next_action()?;
-        match action {
-            Action::Step(step) => {
-                let next_position = step.take_step_from_position(self.prev_position)?;
-                let cell = self.grid.get(next_position)?;
-                self.repeat_count += 1;
-                self.prev_position = next_position;
-                Some(cell)
+        loop {
+            if self.blocked || self.repeation_done().is_none() {
+                self.blocked = false;
+                self.segment_done()?;
+                continue;
             }
-            Action::StepFromOrigin(step) => {
-                let mut next_position = step.take_step_from_position(self.origin_position);
-                while next_position.is_none()
-                    || self
+            let action = match self.pattern.next_action() {
+                Some(action) => action,
+                None => {
+                    self.segment_done()?;
+                    continue;
+                }
+            };
+            let next_position = match action {
+                Action::Step(step) => {
+                    let next_position = self
                         .grid
-                        .get(next_position.unwrap_or((0, 0).into()))
-                        .is_none()
-                {
-                    let action = self.pattern.next_action()?;
-                    let step = match action {
-                        Action::StepFromOrigin(step) => step,
-                        _ => panic!("different actions per pattern not supported"),
-                    };
-                    next_position = step.take_step_from_position(self.origin_position);
+                        .step(self.prev_position, step)
+                        .filter(|&pos| self.ray_done(step, pos).is_some());
+                    match next_position {
+                        Some(pos) => pos,
+                        None => {
+                            self.segment_done()?;
+                            continue;
+                        }
+                    }
                 }
-                // next_position can only be valid here since we check it in the while loop aboe
-                let next_position = next_position.unwrap();
-                let cell = self.grid.get(next_position)?;
-                self.repeat_count += 1;
-                self.prev_position = next_position;
-                Some(cell)
-            }
-            Action::Jump(pos) => {
-                let mut pos = pos;
-                while !self.grid.is_bounds(pos) {
-                    let action = self.pattern.next_action()?;
-                    pos = match action {
-                        Action::Jump(p) => p,
-                        _ => panic!("different actions per pattern not supported"),
-                    };
+                // Steps from the origin and jumps which do not reach into the grid are skipped
+                // without ending the segment, see the `Pattern` docs for endless patterns
+                Action::StepFromOrigin(step) => {
+                    self.is_ray = false;
+                    match self.grid.step(self.origin_position, step) {
+                        Some(pos) => pos,
+                        None => continue,
+                    }
+                }
+                Action::Jump(pos) => {
+                    self.is_ray = false;
+                    match self.grid.wrap_position(pos) {
+                        Some(pos) => pos,
+                        None => continue,
+                    }
+                }
+            };
+            let cell = self.grid.get_unchecked(next_position);
+            let walks = matches!(action, Action::Step(_));
+            let (blocked, inclusive) = match &self.blocking {
+                Some(blocking) => ((blocking.blocks)(cell, next_position), blocking.inclusive),
+                None => (false, false),
+            };
+            if blocked && !inclusive {
+                if walks {
+                    self.segment_done()?;
                 }
-                let cell = self.grid.get_unchecked(pos);
-                self.repeat_count += 1;
-                self.prev_position = pos;
-                Some(cell)
+                continue;
             }
+            self.repeat_count += 1;
+            self.prev_position = next_position;
+            self.blocked = blocked && walks;
+            if self.pattern.distinct() && !self.visited.insert(next_position) {
+                continue;
+            }
+            return Some(cell);
         }
     }
 }
@@ -77,58 +231,47 @@ impl<'a, T> Iterator for PatternIter<'a, T> {
 impl<'a, T> PositionsEnumerator for PatternIter<'a, T> {
     fn grid_positions(self) -> Positions<Self> {
         Positions {
-            prev_position: Some(self.prev_position),
-            next_pos: |inner, prev| {
-                if let Some(action) = inner.pattern.next_action_peek() {
-                    return match action {
-                        Action::Step(step) => {
-                            //prev can not be None in this case, since we set prev_position
-                            step.take_step_from_position(prev.unwrap())
-                                .unwrap_or_default()
-                        }
-                        Action::StepFromOrigin(step) => {
-                            let origin_position = inner.origin_position;
-                            let mut step = step;
-                            let mut next_position = step.take_step_from_position(origin_position);
-                            let steps = inner
-                                .pattern
-                                .rest_steps()
-                                .expect("Implement fn rest_positionn");
-                            let mut steps = steps.iter();
-                            // We can unwrap_or in this while loop since if we are at the end of the
-                            // iterator, next is called till it will return None in PositionEnuemrator
-                            // Unwarp should always be (0, 0) since then we can leave the loop at the end
-                            while next_position.is_none()
-                                || !inner.grid.is_bounds(next_position.unwrap_or((0, 0).into()))
-                            {
-                                step = *steps.next().unwrap_or(&(0, 0).into());
-                                next_position = step.take_step_from_position(origin_position);
-                            }
-                            // This can only be Some since we checka
-                            next_position.unwrap()
-                        }
-                        Action::Jump(pos) => {
-                            let mut pos = pos;
-                            let rest_positions = inner
-                                .pattern
-                                .rest_positions()
-                                .expect("Please implement rest_position for pattern");
-                            let mut positions = rest_positions.iter();
-                            // Ignore positions no in grid
-                            // We can unwrap_or here since the inner iterator next will return None anyway
-                            // if positions is exhausted.
-                            while !inner.grid.is_bounds(pos) {
-                                pos = *positions.next().unwrap_or(&(0, 0).into());
-                            }
-                            pos
-                        }
-                    };
-                } else {
-                    // Since we call .next() for the inner later,
-                    // we should not do any bound checks here.
-                    (0, 0).into()
-                }
-            },
+            last_pos: |inner| inner.prev_position,
+            last_back_pos: |inner| inner.prev_position,
+            inner: self,
+        }
+    }
+}
+
+pub struct PatternIterMut<'a, T> {
+    pub(crate) positions: PositionList,
+    pub(crate) cells: std::vec::IntoIter<&'a mut T>,
+}
+
+impl<'a, T> Iterator for PatternIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.positions.next()?;
+        self.cells.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for PatternIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.positions.next_back()?;
+        self.cells.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PatternIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for PatternIterMut<'a, T> {}
+
+impl<'a, T> PositionsEnumerator for PatternIterMut<'a, T> {
+    fn grid_positions(self) -> Positions<Self> {
+        Positions {
+            last_pos: |inner| inner.positions.last_front(),
+            last_back_pos: |inner| inner.positions.last_back(),
             inner: self,
         }
     }
@@ -137,7 +280,10 @@ impl<'a, T> PositionsEnumerator for PatternIter<'a, T> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::pattern::{DirectionPattern, JumpsPattern, SideStepsPattern, StepsPattern};
+    use crate::pattern::{
+        ActionsPattern, DirectionPattern, JumpsPattern, Pattern, SideStepsPattern, StepsPattern,
+    };
+    use crate::{Grid, Symmetry, Wrap};
 
     // 0, 1, 2, 3
     // 4, 5, 6, 7
@@ -377,4 +523,369 @@ mod test {
         assert_eq!(iter.next(), Some(((1, 0).into(), &1)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn pattern_iter_mut_direction() {
+        let mut grid = Grid {
+            width: 4,
+            height: 4,
+            items: (0..16).collect(),
+        };
+
+        let pattern = DirectionPattern::new((0, -1), Repeat::TillEnd);
+        grid.pattern_mut((1, 3), pattern).for_each(|cell| *cell = 0);
+        assert_eq!(grid.get((1, 3)), Some(&13));
+        assert_eq!(grid.get((1, 2)), Some(&0));
+        assert_eq!(grid.get((1, 1)), Some(&0));
+        assert_eq!(grid.get((1, 0)), Some(&0));
+    }
+
+    #[test]
+    fn pattern_iter_mut_steps_skips_revisits() {
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+
+        let seq: Vec<(i32, i32)> = vec![(0, -1), (0, 1), (1, 0), (-1, 0), (0, 1)];
+        let pattern = StepsPattern::new(seq);
+        let mut iter = grid.pattern_mut((1, 1), pattern);
+        assert_eq!(iter.next(), Some(&mut 1));
+        assert_eq!(iter.next(), Some(&mut 4));
+        assert_eq!(iter.next(), Some(&mut 5));
+        assert_eq!(iter.next(), Some(&mut 7));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn pattern_iter_mut_positions() {
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+
+        let jumps = vec![(2, 2), (5, 5), (1, 1), (2, 2), (1, 0)];
+        let pattern = JumpsPattern::new(jumps);
+        let mut iter = grid.pattern_mut((0, 0), pattern).grid_positions();
+        assert_eq!(iter.next(), Some(((2, 2).into(), &mut 8)));
+        assert_eq!(iter.next(), Some(((1, 1).into(), &mut 4)));
+        assert_eq!(iter.next(), Some(((1, 0).into(), &mut 1)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn pattern_iter_mut_double_ended_positions() {
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+
+        let pattern = JumpsPattern::new(vec![(2, 2), (1, 1), (1, 0)]);
+        let mut iter = grid.pattern_mut((0, 0), pattern).grid_positions();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(((1, 0).into(), &mut 1)));
+        assert_eq!(iter.next(), Some(((2, 2).into(), &mut 8)));
+        assert_eq!(iter.next_back(), Some(((1, 1).into(), &mut 4)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn pattern_iter_wrapping_direction_ends_before_origin() {
+        let grid = Grid {
+            width: 4,
+            height: 4,
+            items: (0..16).collect(),
+        };
+
+        let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd);
+        let mut iter = grid.wrapping(Wrap::Horizontal).pattern((2, 1), pattern);
+        assert_eq!(iter.next(), Some(&7));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.next(), None);
+
+        let pattern = DirectionPattern::new((2, -1), Repeat::TillEnd);
+        let mut iter = grid
+            .wrapping(Wrap::Both)
+            .pattern((0, 0), pattern)
+            .grid_positions();
+        assert_eq!(iter.next(), Some(((2, 3).into(), &14)));
+        assert_eq!(iter.next(), Some(((0, 2).into(), &8)));
+        assert_eq!(iter.next(), Some(((2, 1).into(), &6)));
+        assert_eq!(iter.next(), None);
+
+        // A ray on a cylinder still ends at the border of the not wrapping axis
+        let pattern = DirectionPattern::new((1, 1), Repeat::TillEnd);
+        let iter = grid.wrapping(Wrap::Horizontal).pattern((3, 1), pattern);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&8, &13]);
+    }
+
+    #[test]
+    fn pattern_iter_wrapping_direction_times() {
+        let grid = Grid {
+            width: 3,
+            height: 1,
+            items: (0..3).collect(),
+        };
+
+        let pattern = DirectionPattern::new((-1, 0), Repeat::Times(4));
+        let iter = grid.wrapping(Wrap::Both).pattern((0, 0), pattern);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &1, &0, &2]);
+
+        let pattern = DirectionPattern::new((0, 0), Repeat::TillEnd);
+        let mut iter = grid.wrapping(Wrap::Both).pattern((0, 0), pattern);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn pattern_iter_wrapping_steps_sidesteps_and_jumps() {
+        let grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+        let torus = grid.wrapping(Wrap::Both);
+
+        // Walks may pass the origin, only straight rays end there
+        let steps: Vec<(i32, i32)> = vec![(-1, 0), (1, 0), (0, -1)];
+        let mut iter = torus
+            .pattern((0, 0), StepsPattern::new(steps))
+            .grid_positions();
+        assert_eq!(iter.next(), Some(((2, 0).into(), &2)));
+        assert_eq!(iter.next(), Some(((0, 0).into(), &0)));
+        assert_eq!(iter.next(), Some(((0, 2).into(), &6)));
+        assert_eq!(iter.next(), None);
+
+        let sidesteps = vec![(-1, -1), (4, 0)];
+        let mut iter = torus
+            .pattern((0, 0), SideStepsPattern::new(sidesteps))
+            .grid_positions();
+        assert_eq!(iter.next(), Some(((2, 2).into(), &8)));
+        assert_eq!(iter.next(), Some(((1, 0).into(), &1)));
+        assert_eq!(iter.next(), None);
+
+        let jumps = vec![(3, 4), (1, 1)];
+        let mut iter = torus
+            .pattern((0, 0), JumpsPattern::new(jumps))
+            .grid_positions();
+        assert_eq!(iter.next(), Some(((0, 1).into(), &3)));
+        assert_eq!(iter.next(), Some(((1, 1).into(), &4)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn pattern_iter_mixed_actions_positions() {
+        let grid = Grid {
+            width: 4,
+            height: 4,
+            items: (0..16).collect(),
+        };
+
+        // Invalid side steps and jumps are skipped, steps continue from the last cell
+        let actions = vec![
+            Action::Step((1, 0).into()),
+            Action::StepFromOrigin((-1, 0).into()),
+            Action::StepFromOrigin((0, 2).into()),
+            Action::Step((1, 0).into()),
+            Action::Jump((9, 9).into()),
+            Action::Jump((3, 0).into()),
+            Action::Step((0, 1).into()),
+            Action::Step((1, 0).into()),
+            Action::Step((0, 1).into()),
+        ];
+        let mut iter = grid
+            .pattern((0, 1), ActionsPattern::new(actions))
+            .grid_positions();
+        assert_eq!(iter.next(), Some(((1, 1).into(), &5)));
+        assert_eq!(iter.next(), Some(((0, 3).into(), &12)));
+        assert_eq!(iter.next(), Some(((1, 3).into(), &13)));
+        assert_eq!(iter.next(), Some(((3, 0).into(), &3)));
+        assert_eq!(iter.next(), Some(((3, 1).into(), &7)));
+        // A step outside of the grid ends the pattern
+        assert_eq!(iter.next(), None);
+    }
+
+    struct TwoCycles {
+        count: usize,
+    }
+
+    impl Pattern for TwoCycles {
+        fn next_action(&mut self) -> Option<Action> {
+            self.count += 1;
+            match self.count % 2 {
+                0 => Some(Action::Jump((0, 0).into())),
+                _ => Some(Action::Step((1, 1).into())),
+            }
+        }
+
+        fn repeat(&self) -> &Repeat {
+            &Repeat::Times(5)
+        }
+    }
+
+    #[test]
+    fn pattern_iter_mixed_actions_repeat() {
+        let grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+        let iter = grid.pattern((1, 1), TwoCycles { count: 0 });
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&8, &0, &4, &0, &4]);
+
+        // Jumping back to the origin does not end the pattern on a wrapping grid
+        let iter = grid
+            .wrapping(Wrap::Both)
+            .pattern((0, 0), TwoCycles { count: 0 });
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&4, &0, &4, &0, &4]);
+
+        let mut grid = grid;
+        let iter = grid.pattern_mut((1, 1), TwoCycles { count: 0 });
+        assert_eq!(iter.count(), 3);
+    }
+
+    // Jumps through the cells of the first row forever
+    struct EndlessJumps {
+        count: usize,
+        width: usize,
+    }
+
+    impl Pattern for EndlessJumps {
+        fn next_action(&mut self) -> Option<Action> {
+            self.count += 1;
+            Some(Action::Jump((self.count % self.width, 0).into()))
+        }
+
+        fn repeat(&self) -> &Repeat {
+            &Repeat::TillEnd
+        }
+    }
+
+    #[test]
+    fn pattern_iter_mut_stops_when_every_cell_is_visited() {
+        let mut grid = Grid {
+            width: 3,
+            height: 1,
+            items: (0..3).collect(),
+        };
+        let iter = grid.pattern_mut((0, 0), EndlessJumps { count: 0, width: 3 });
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&mut 1, &mut 2, &mut 0]);
+    }
+
+    #[test]
+    fn pattern_iter_until_blocked() {
+        let grid = Grid {
+            width: 4,
+            height: 4,
+            items: vec![0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1],
+        };
+        let blocks = |cell: &i32, _| *cell == 1;
+
+        let ray = DirectionPattern::new((1, 0), Repeat::TillEnd);
+        let iter = grid.pattern((0, 0), ray).until_blocked(blocks);
+        assert_eq!(iter.grid_positions().count(), 1);
+        let mut iter = grid
+            .pattern((0, 0), ray)
+            .until_blocked_inclusive(blocks)
+            .grid_positions();
+        assert_eq!(iter.next(), Some(((1, 0).into(), &0)));
+        assert_eq!(iter.next(), Some(((2, 0).into(), &1)));
+        assert_eq!(iter.next(), None);
+
+        // Every ray of a combined pattern is blocked on its own
+        let queen = DirectionPattern::new((1, 0), Repeat::TillEnd)
+            .rotations4()
+            .chain(DirectionPattern::new((1, 1), Repeat::TillEnd).rotations4());
+        let moves: Vec<Position> = grid
+            .pattern((0, 0), queen)
+            .until_blocked_inclusive(blocks)
+            .grid_positions()
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                (1, 0).into(),
+                (2, 0).into(),
+                (0, 1).into(),
+                (0, 2).into(),
+                (1, 1).into(),
+                (2, 2).into(),
+                (3, 3).into()
+            ]
+        );
+
+        // Blocking side steps and jumps are skipped, the pattern goes on
+        let actions = vec![
+            Action::StepFromOrigin((2, 0).into()),
+            Action::Jump((0, 2).into()),
+            Action::StepFromOrigin((1, 1).into()),
+            Action::Step((1, 0).into()),
+        ];
+        let iter = grid
+            .pattern((0, 0), ActionsPattern::new(actions))
+            .until_blocked(blocks);
+        assert_eq!(iter.grid_positions().count(), 2);
+    }
+
+    #[test]
+    fn pattern_iter_until_blocked_repeat_and_wrap() {
+        let grid = Grid {
+            width: 5,
+            height: 1,
+            items: vec![0, 0, 0, 1, 0],
+        };
+        let blocks = |cell: &i32, _| *cell == 1;
+
+        let ray = DirectionPattern::new((1, 0), Repeat::Times(2));
+        let iter = grid.pattern((0, 0), ray).until_blocked(blocks);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&0, &0]);
+
+        // On a wrapping grid the blocking cell ends the ray before it reaches the origin
+        let ray = DirectionPattern::new((-1, 0), Repeat::TillEnd);
+        let iter = grid
+            .wrapping(Wrap::Horizontal)
+            .pattern((1, 0), ray)
+            .until_blocked_inclusive(blocks)
+            .grid_positions()
+            .map(|(pos, _)| pos.x);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 4, 3]);
+    }
+
+    #[test]
+    fn pattern_iter_steps_with_symmetry() {
+        let grid = Grid {
+            width: 4,
+            height: 4,
+            items: (0..16).collect(),
+        };
+
+        // Every image is walked from the origin, a walk ends when it leaves the grid
+        let steps: Vec<(i32, i32)> = vec![(1, 0), (1, 0), (0, 1)];
+        let pattern = StepsPattern::with_symmetry(steps, Symmetry::MirrorX);
+        let mut iter = grid.pattern((1, 1), pattern).grid_positions();
+        assert_eq!(iter.next(), Some(((2, 1).into(), &6)));
+        assert_eq!(iter.next(), Some(((3, 1).into(), &7)));
+        assert_eq!(iter.next(), Some(((3, 2).into(), &11)));
+        assert_eq!(iter.next(), Some(((0, 1).into(), &4)));
+        assert_eq!(iter.next(), None);
+
+        // Images which are equal are only walked once
+        let pattern = StepsPattern::with_symmetry(vec![(0, 1)], Symmetry::MirrorX);
+        assert_eq!(grid.pattern((0, 0), pattern).count(), 1);
+
+        // A camel leaps (1, 3) in all 8 directions
+        let camel = SideStepsPattern::with_symmetry(vec![(1, 3)], Symmetry::All);
+        let mut moves: Vec<Position> = grid
+            .pattern((1, 0), camel)
+            .grid_positions()
+            .map(|(pos, _)| pos)
+            .collect();
+        moves.sort_by_key(|pos| pos.x);
+        assert_eq!(moves, vec![(0, 3).into(), (2, 3).into()]);
+    }
 }
@@ -1,9 +1,15 @@
 use crate::grid::Position;
+use std::iter::FusedIterator;
 
+/// Iterator which yields the position of every item of the inner iterator,
+/// created by [PositionsEnumerator::grid_positions].
 pub struct Positions<I> {
     pub(crate) inner: I,
-    pub(crate) next_pos: fn(&I, Option<Position>) -> Position,
-    pub(crate) prev_position: Option<Position>,
+    // Returns the position of the item last returned by inner.next()
+    pub(crate) last_pos: fn(&I) -> Position,
+    // Returns the position of the item last returned by inner.next_back(),
+    // only called if the inner iterator is double ended.
+    pub(crate) last_back_pos: fn(&I) -> Position,
 }
 
 /// Enumerates the positions of the grid.
@@ -19,6 +25,10 @@ pub struct Positions<I> {
 /// assert_eq!(iter.next(), Some(((0, 0).into(), &1)));
 /// assert_eq!(iter.next(), Some(((0, 1).into(), &1)));
 /// assert_eq!(iter.next(), None);
+///
+/// // positions stay correct when iterating from the back
+/// let mut iter = grid.row(1).grid_positions().rev();
+/// assert_eq!(iter.next(), Some(((1, 1).into(), &2)));
 /// ```
 pub trait PositionsEnumerator
 where
@@ -30,8 +40,22 @@ where
 impl<I: Iterator> Iterator for Positions<I> {
     type Item = (Position, I::Item);
     fn next(&mut self) -> Option<Self::Item> {
-        let next_pos = (self.next_pos)(&self.inner, self.prev_position);
-        self.prev_position = Some(next_pos);
-        Some((next_pos, self.inner.next()?))
+        let item = self.inner.next()?;
+        Some(((self.last_pos)(&self.inner), item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Positions<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next_back()?;
+        Some(((self.last_back_pos)(&self.inner), item))
     }
 }
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Positions<I> {}
+
+impl<I: FusedIterator> FusedIterator for Positions<I> {}
@@ -1,24 +1,50 @@
 use crate::grid::Position;
+use std::iter::FusedIterator;
 
 pub struct PositionsIter {
-    pub(crate) len: usize,
+    // index after the next position from the back
+    pub(crate) end: usize,
     pub(crate) width: usize,
+    // index of the next position from the front
     pub(crate) idx: usize,
 }
 
+impl PositionsIter {
+    fn position_at(&self, idx: usize) -> Position {
+        (idx % self.width, idx / self.width).into()
+    }
+}
+
 impl Iterator for PositionsIter {
     type Item = Position;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.idx >= self.len {
+        if self.idx >= self.end {
             return None;
         }
-        let x = self.idx % self.width;
-        let y = self.idx / self.width;
         self.idx += 1;
-        Some((x, y).into())
+        Some(self.position_at(self.idx - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
     }
 }
 
+impl DoubleEndedIterator for PositionsIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.position_at(self.end))
+    }
+}
+
+impl ExactSizeIterator for PositionsIter {}
+
+impl FusedIterator for PositionsIter {}
+
 #[cfg(test)]
 mod tests {
     use crate::Grid;
@@ -40,4 +66,23 @@ mod tests {
         assert_eq!(positions.next(), Some((2, 1).into()));
         assert_eq!(positions.next(), None);
     }
+
+    #[test]
+    fn grid_positions_iter_rev() {
+        let grid = Grid {
+            width: 2,
+            height: 2,
+            items: vec![0; 2 * 2],
+        };
+
+        let mut positions = grid.positions();
+        assert_eq!(positions.len(), 4);
+        assert_eq!(positions.next_back(), Some((1, 1).into()));
+        assert_eq!(positions.next(), Some((0, 0).into()));
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions.next_back(), Some((0, 1).into()));
+        assert_eq!(positions.next_back(), Some((1, 0).into()));
+        assert_eq!(positions.next_back(), None);
+        assert_eq!(positions.next(), None);
+    }
 }
@@ -0,0 +1,64 @@
+use crate::grid::Position;
+use std::iter::FusedIterator;
+
+// Precomputed positions, yielded from the front and the back.
+// The iterators over the cells of these positions advance it in lockstep,
+// so it also knows the positions of the cells they yielded last.
+pub(crate) struct PositionList {
+    positions: Vec<Position>,
+    // index of the next position from the front
+    idx: usize,
+    // index after the next position from the back
+    end: usize,
+}
+
+impl PositionList {
+    pub(crate) fn new(positions: Vec<Position>) -> Self {
+        Self {
+            end: positions.len(),
+            positions,
+            idx: 0,
+        }
+    }
+
+    // Returns the position last yielded from the front
+    pub(crate) fn last_front(&self) -> Position {
+        self.positions[self.idx - 1]
+    }
+
+    // Returns the position last yielded from the back
+    pub(crate) fn last_back(&self) -> Position {
+        self.positions[self.end]
+    }
+}
+
+impl Iterator for PositionList {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.end {
+            return None;
+        }
+        self.idx += 1;
+        Some(self.positions[self.idx - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for PositionList {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.positions[self.end])
+    }
+}
+
+impl ExactSizeIterator for PositionList {}
+
+impl FusedIterator for PositionList {}
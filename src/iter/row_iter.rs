@@ -1,25 +1,41 @@
 use crate::iter::{Positions, PositionsEnumerator};
+use std::iter::FusedIterator;
 
 pub struct RowIter<'a, T> {
     pub(crate) row_iter: std::slice::Iter<'a, T>,
     pub(crate) idx: usize,
+    // x of the next cell from the front
+    pub(crate) x: usize,
 }
 
 impl<'a, T> Iterator for RowIter<'a, T> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
-        self.row_iter.next()
+        let cell = self.row_iter.next()?;
+        self.x += 1;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.row_iter.size_hint()
     }
 }
 
+impl<'a, T> DoubleEndedIterator for RowIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.row_iter.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RowIter<'a, T> {}
+
+impl<'a, T> FusedIterator for RowIter<'a, T> {}
+
 impl<'a, T: 'static> PositionsEnumerator for RowIter<'a, T> {
     fn grid_positions(self) -> Positions<Self> {
         Positions {
-            next_pos: |inner, prev_pos| match prev_pos {
-                None => (0, inner.idx).into(),
-                Some(p) => (p.x + 1, p.y).into(),
-            },
-            prev_position: None,
+            last_pos: |inner| (inner.x - 1, inner.idx).into(),
+            last_back_pos: |inner| (inner.x + inner.len(), inner.idx).into(),
             inner: self,
         }
     }
@@ -28,23 +44,38 @@ impl<'a, T: 'static> PositionsEnumerator for RowIter<'a, T> {
 pub struct RowIterMut<'a, T> {
     pub(crate) row_iter: std::slice::IterMut<'a, T>,
     pub(crate) idx: usize,
+    // x of the next cell from the front
+    pub(crate) x: usize,
 }
 
 impl<'a, T> Iterator for RowIterMut<'a, T> {
     type Item = &'a mut T;
     fn next(&mut self) -> Option<Self::Item> {
-        self.row_iter.next()
+        let cell = self.row_iter.next()?;
+        self.x += 1;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.row_iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RowIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.row_iter.next_back()
     }
 }
 
+impl<'a, T> ExactSizeIterator for RowIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for RowIterMut<'a, T> {}
+
 impl<'a, T: 'static> PositionsEnumerator for RowIterMut<'a, T> {
     fn grid_positions(self) -> Positions<Self> {
         Positions {
-            next_pos: |inner, prev_pos| match prev_pos {
-                None => (0, inner.idx).into(),
-                Some(p) => (p.x + 1, p.y).into(),
-            },
-            prev_position: None,
+            last_pos: |inner| (inner.x - 1, inner.idx).into(),
+            last_back_pos: |inner| (inner.x + inner.len(), inner.idx).into(),
             inner: self,
         }
     }
@@ -191,4 +222,39 @@ mod tests {
         assert_eq!(row_pos.next(), Some(((3, 0).into(), &mut 0)));
         assert_eq!(row_pos.next(), None);
     }
+
+    #[test]
+    fn row_iter_double_ended_positions() {
+        let mut grid = Grid {
+            width: 4,
+            height: 2,
+            items: (0..8).collect(),
+        };
+
+        let mut row = grid.row(1);
+        assert_eq!(row.len(), 4);
+        assert_eq!(row.next_back(), Some(&7));
+        assert_eq!(row.len(), 3);
+        assert_eq!(row.rposition(|cell| *cell == 4), Some(0));
+
+        let mut row_pos = grid.row(1).grid_positions();
+        assert_eq!(row_pos.next_back(), Some(((3, 1).into(), &7)));
+        assert_eq!(row_pos.next(), Some(((0, 1).into(), &4)));
+        assert_eq!(row_pos.next_back(), Some(((2, 1).into(), &6)));
+        assert_eq!(row_pos.len(), 1);
+        assert_eq!(row_pos.next(), Some(((1, 1).into(), &5)));
+        assert_eq!(row_pos.next_back(), None);
+        assert_eq!(row_pos.next(), None);
+
+        let positions: Vec<_> = grid
+            .row_mut(0)
+            .grid_positions()
+            .rev()
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(
+            positions,
+            vec![(3, 0).into(), (2, 0).into(), (1, 0).into(), (0, 0).into()]
+        );
+    }
 }
@@ -0,0 +1,84 @@
+use super::{PositionList, Positions, PositionsEnumerator};
+use crate::view::GridView;
+use std::iter::FusedIterator;
+
+/// Iterator over the cells of a shape like a line or a circle, e.g. created by [Grid::line](crate::Grid::line).
+pub struct ShapeIter<'a, T> {
+    pub(crate) positions: PositionList,
+    pub(crate) grid: GridView<'a, T>,
+}
+
+impl<'a, T> Iterator for ShapeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next()?;
+        Some(self.grid.get_unchecked(pos))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ShapeIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next_back()?;
+        Some(self.grid.get_unchecked(pos))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ShapeIter<'a, T> {}
+
+impl<'a, T> FusedIterator for ShapeIter<'a, T> {}
+
+impl<'a, T> PositionsEnumerator for ShapeIter<'a, T> {
+    fn grid_positions(self) -> Positions<Self> {
+        Positions {
+            last_pos: |inner| inner.positions.last_front(),
+            last_back_pos: |inner| inner.positions.last_back(),
+            inner: self,
+        }
+    }
+}
+
+/// Iterator over mutable references of the cells of a shape,
+/// e.g. created by [Grid::line_mut](crate::Grid::line_mut).
+pub struct ShapeIterMut<'a, T> {
+    pub(crate) positions: PositionList,
+    pub(crate) cells: std::vec::IntoIter<&'a mut T>,
+}
+
+impl<'a, T> Iterator for ShapeIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.positions.next()?;
+        self.cells.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ShapeIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.positions.next_back()?;
+        self.cells.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ShapeIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for ShapeIterMut<'a, T> {}
+
+impl<'a, T> PositionsEnumerator for ShapeIterMut<'a, T> {
+    fn grid_positions(self) -> Positions<Self> {
+        Positions {
+            last_pos: |inner| inner.positions.last_front(),
+            last_back_pos: |inner| inner.positions.last_back(),
+            inner: self,
+        }
+    }
+}
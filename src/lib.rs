@@ -5,11 +5,26 @@
 //! E.g get the neighbor cells of a position with [Grid::neighbors] or
 //! cells depending of a pattern from a given position with [Grid::pattern].
 
+mod error;
+mod fov;
 mod grid;
 pub mod iter;
+mod neighborhood;
+#[cfg(feature = "rayon")]
+mod par;
+pub mod pathfinding;
 pub mod pattern;
+mod region;
+mod shape;
 mod step;
+mod text;
+mod transform;
+mod view;
 
-pub use grid::{Grid, Position};
+pub use error::{BetzaError, GridError};
+pub use grid::{Anchor, Grid, Position, Rect};
 pub use iter::PositionsEnumerator;
-pub use step::Step;
+pub use neighborhood::Neighborhood;
+pub use step::{Step, Symmetry};
+pub use text::DisplayWith;
+pub use view::{GridView, GridViewMut, Wrap};
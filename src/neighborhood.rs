@@ -0,0 +1,208 @@
+use crate::Step;
+use std::collections::HashSet;
+
+/// The steps from a position to its neighbors, used by [Grid::neighbors_with](crate::Grid::neighbors_with).
+/// Neighbors are yielded in the order of the steps.
+/// # Example
+/// ```
+/// # use gridit::{Grid, Neighborhood};
+/// // 0, 1, 2
+/// // 3, 4, 5
+/// // 6, 7, 8
+/// let grid = Grid::from((0..9).collect(), 3, 3);
+/// let neighbors: Vec<_> = grid.neighbors_with((1, 1), &Neighborhood::von_neumann()).collect();
+/// assert_eq!(neighbors, vec![&1, &3, &5, &7]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Neighborhood {
+    steps: Vec<Step>,
+}
+
+impl Neighborhood {
+    /// The 8 surrounding cells, this is the neighborhood of [Grid::neighbors](crate::Grid::neighbors).
+    pub fn moore() -> Self {
+        Self::moore_radius(1)
+    }
+
+    /// Every cell within `radius` steps in any direction, including diagonal steps.
+    /// The steps are ordered from the top left to the bottom right.
+    /// # Example
+    /// ```
+    /// # use gridit::Neighborhood;
+    /// assert_eq!(Neighborhood::moore_radius(2).steps().len(), 24);
+    /// ```
+    pub fn moore_radius(radius: usize) -> Self {
+        Self::from_fn(radius, |_, _| true)
+    }
+
+    /// The 4 orthogonally adjacent cells.
+    pub fn von_neumann() -> Self {
+        Self::von_neumann_radius(1)
+    }
+
+    /// Every cell within `radius` orthogonal steps.
+    /// The steps are ordered from the top left to the bottom right.
+    /// # Example
+    /// ```
+    /// # use gridit::Neighborhood;
+    /// assert_eq!(Neighborhood::von_neumann_radius(2).steps().len(), 12);
+    /// ```
+    pub fn von_neumann_radius(radius: usize) -> Self {
+        Self::from_fn(radius, |x, y| x.unsigned_abs() + y.unsigned_abs() <= radius)
+    }
+
+    /// A neighborhood of custom steps, a repeated step is only kept the first time.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood};
+    /// // Neighbors on a hex grid with odd rows shifted to the right
+    /// let hex_odd_row = Neighborhood::custom(vec![(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)]);
+    /// let grid = Grid::new(3, 3, 0);
+    /// assert_eq!(grid.neighbors_with((1, 1), &hex_odd_row).count(), 6);
+    /// ```
+    pub fn custom<I>(steps: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Step>,
+    {
+        let mut seen = HashSet::new();
+        Self {
+            steps: steps
+                .into_iter()
+                .map(|step| step.into())
+                .filter(|step| seen.insert(*step))
+                .collect(),
+        }
+    }
+
+    /// Returns the steps of the neighborhood.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    // Every step in the square of `radius` around (0, 0) for which `include` returns true
+    fn from_fn<F>(radius: usize, include: F) -> Self
+    where
+        F: Fn(isize, isize) -> bool,
+    {
+        let radius = radius as isize;
+        let mut steps = Vec::new();
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                if (x, y) != (0, 0) && include(x, y) {
+                    steps.push((x, y).into());
+                }
+            }
+        }
+        Self { steps }
+    }
+}
+
+// Unchecked neighborhood data, the steps are deduplicated on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Neighborhood")]
+struct RawNeighborhood {
+    steps: Vec<Step>,
+}
+
+/// Deserializes the neighborhood, a repeated step is only kept the first time.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Neighborhood {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = RawNeighborhood::deserialize(deserializer)?;
+        Ok(Self::custom(raw.steps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, PositionsEnumerator};
+
+    #[test]
+    fn von_neumann_radius_steps() {
+        let steps: Vec<Step> = vec![
+            (0, -2),
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-2, 0),
+            (-1, 0),
+            (1, 0),
+            (2, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+            (0, 2),
+        ]
+        .into_iter()
+        .map(|step: (i32, i32)| step.into())
+        .collect();
+        assert_eq!(Neighborhood::von_neumann_radius(2).steps(), &steps[..]);
+    }
+
+    #[test]
+    fn neighbors_with_moore_radius() {
+        // 0, 1, 2, 3
+        // 4, 5, 6, 7
+        // 8, 9,10,11
+        let grid = Grid {
+            width: 4,
+            height: 3,
+            items: (0..12).collect(),
+        };
+
+        let neighbors: Vec<_> = grid
+            .neighbors_with((0, 0), &Neighborhood::moore_radius(2))
+            .collect();
+        assert_eq!(neighbors, vec![&1, &2, &4, &5, &6, &8, &9, &10]);
+        assert_eq!(
+            grid.neighbors_with((1, 1), &Neighborhood::moore()).count(),
+            grid.neighbors((1, 1)).count()
+        );
+    }
+
+    #[test]
+    fn neighbors_with_custom_positions() {
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+        let knight = Neighborhood::custom(vec![(1, 2), (2, 1), (-1, -2), (-2, -1)]);
+
+        let mut neighbors = grid.neighbors_with((0, 0), &knight).grid_positions();
+        assert_eq!(neighbors.next(), Some(((1, 2).into(), &7)));
+        assert_eq!(neighbors.next(), Some(((2, 1).into(), &5)));
+        assert_eq!(neighbors.next(), None);
+
+        grid.neighbors_with_mut((2, 2), &knight)
+            .for_each(|cell| *cell = 0);
+        assert_eq!(grid.items, vec![0, 0, 2, 0, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn custom_drops_repeated_steps() {
+        let neighborhood = Neighborhood::custom(vec![(1, 0), (0, 1), (1, 0)]);
+        let steps: Vec<Step> = vec![Step::new(1, 0), Step::new(0, 1)];
+        assert_eq!(neighborhood.steps(), &steps[..]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_drops_repeated_steps() {
+        let json = r#"{"steps":[{"x":1,"y":0},{"x":1,"y":0}]}"#;
+        let neighborhood: Neighborhood = serde_json::from_str(json).unwrap();
+        assert_eq!(neighborhood.steps(), &[Step::new(1, 0)][..]);
+        let json = serde_json::to_string(&neighborhood).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Neighborhood>(&json).unwrap(),
+            neighborhood
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::grid::{Grid, Position};
+use rayon::prelude::*;
+use rayon::slice::{Chunks, ChunksMut, Iter, IterMut};
+
+impl<T: Sync> Grid<T> {
+    /// Creates a parallel iterator which yields references of every element in grid.
+    /// Requires the `rayon` feature.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use rayon::prelude::*;
+    /// let grid = Grid::from((0..16).collect(), 4, 4);
+    /// let sum: i32 = grid.par_iter().sum();
+    /// assert_eq!(sum, 120);
+    /// ```
+    pub fn par_iter(&self) -> Iter<'_, T> {
+        self.items.par_iter()
+    }
+
+    /// Creates a parallel iterator which yields the position and a reference of every element in grid.
+    /// Requires the `rayon` feature.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Position};
+    /// use rayon::prelude::*;
+    /// let grid = Grid::from(vec![1, 2, 1, 2], 2, 2);
+    /// let ones: Vec<Position> = grid
+    ///     .par_iter_positions()
+    ///     .filter(|(_, cell)| **cell == 1)
+    ///     .map(|(pos, _)| pos)
+    ///     .collect();
+    /// assert_eq!(ones, vec![(0, 0).into(), (0, 1).into()]);
+    /// ```
+    pub fn par_iter_positions(&self) -> impl IndexedParallelIterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.items
+            .par_iter()
+            .enumerate()
+            .map(move |(idx, cell)| (Position::new(idx % width, idx / width), cell))
+    }
+
+    /// Creates a parallel iterator which yields every row of the grid as a slice,
+    /// from the top to the bottom. Requires the `rayon` feature.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use rayon::prelude::*;
+    /// let grid = Grid::from((0..6).collect(), 3, 2);
+    /// let sums: Vec<i32> = grid.par_rows().map(|row| row.iter().sum()).collect();
+    /// assert_eq!(sums, vec![3, 12]);
+    /// ```
+    pub fn par_rows(&self) -> Chunks<'_, T> {
+        self.items.par_chunks(self.width)
+    }
+}
+
+impl<T: Send> Grid<T> {
+    /// Creates a parallel iterator which yields mutable references of every element in grid.
+    /// Requires the `rayon` feature.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use rayon::prelude::*;
+    /// let mut grid = Grid::new(4, 4, 1);
+    /// grid.par_iter_mut().for_each(|cell| *cell *= 2);
+    /// assert_eq!(grid, Grid::new(4, 4, 2));
+    /// ```
+    pub fn par_iter_mut(&mut self) -> IterMut<'_, T> {
+        self.items.par_iter_mut()
+    }
+
+    /// Creates a parallel iterator which yields the position and a mutable reference
+    /// of every element in grid. Requires the `rayon` feature.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use rayon::prelude::*;
+    /// let mut grid = Grid::new(3, 2, 0);
+    /// grid.par_iter_positions_mut()
+    ///     .for_each(|(pos, cell)| *cell = pos.x + pos.y);
+    /// assert_eq!(grid, Grid::from(vec![0, 1, 2, 1, 2, 3], 3, 2));
+    /// ```
+    pub fn par_iter_positions_mut(
+        &mut self,
+    ) -> impl IndexedParallelIterator<Item = (Position, &mut T)> {
+        let width = self.width;
+        self.items
+            .par_iter_mut()
+            .enumerate()
+            .map(move |(idx, cell)| (Position::new(idx % width, idx / width), cell))
+    }
+
+    /// Creates a parallel iterator which yields every row of the grid as a mutable slice,
+    /// from the top to the bottom. Requires the `rayon` feature.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use rayon::prelude::*;
+    /// let mut grid = Grid::new(3, 2, 0);
+    /// grid.par_rows_mut()
+    ///     .enumerate()
+    ///     .for_each(|(y, row)| row.fill(y));
+    /// assert_eq!(grid, Grid::from(vec![0, 0, 0, 1, 1, 1], 3, 2));
+    /// ```
+    pub fn par_rows_mut(&mut self) -> ChunksMut<'_, T> {
+        self.items.par_chunks_mut(self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PositionsEnumerator;
+
+    #[test]
+    fn par_iter_matches_iter() {
+        let grid = Grid::from((0..64 * 48).collect(), 64, 48);
+        let par: Vec<_> = grid.par_iter().collect();
+        let seq: Vec<_> = grid.iter().collect();
+        assert_eq!(par, seq);
+
+        let par: Vec<_> = grid.par_iter_positions().collect();
+        let seq: Vec<_> = grid.iter().grid_positions().collect();
+        assert_eq!(par, seq);
+        assert_eq!(grid.par_iter_positions().len(), 64 * 48);
+    }
+
+    #[test]
+    fn par_rows_mut_updates_rows() {
+        let mut grid = Grid::new(5, 40, 0usize);
+        grid.par_rows_mut().enumerate().for_each(|(y, row)| {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = y * 5 + x;
+            }
+        });
+        assert_eq!(grid, Grid::from((0..200).collect(), 5, 40));
+        assert_eq!(grid.par_rows().count(), 40);
+        assert!(grid.par_rows().all(|row| row.len() == 5));
+    }
+
+    #[test]
+    fn par_iter_positions_mut_knows_position() {
+        let mut grid = Grid::new(30, 20, Position::default());
+        grid.par_iter_positions_mut()
+            .for_each(|(pos, cell)| *cell = pos);
+        assert_eq!(grid.positions().collect::<Vec<_>>(), grid.items);
+    }
+}
@@ -0,0 +1,565 @@
+//! Shortest paths between two positions with [bfs], [dijkstra] and [astar].
+//!
+//! All searches take a cost closure `Fn(&T, Position) -> Option<u32>`, which returns
+//! the cost of entering a cell or `None` if the cell is impassable,
+//! and a [Neighborhood] which defines the cells reachable in one step.
+//! The searches run on a [Grid] or on a [GridView], so they also work
+//! on sub regions and wrapping views. Positions are local to the view.
+//!
+//! Distances and directions from every cell to the nearest of many sources
+//! are computed by [Grid::distance_field] and [Grid::flow_field].
+//! # Example
+//! ```
+//! # use gridit::{Grid, Neighborhood, Position};
+//! # use gridit::pathfinding::{astar, manhattan};
+//! let grid = Grid::from_str_map("
+//! ..#.
+//! ..#.
+//! ....
+//! ", |c| c).unwrap();
+//! let cost = |cell: &char, _| if *cell == '#' { None } else { Some(1) };
+//! let path = astar(&grid, (0, 0), (3, 0), &Neighborhood::von_neumann(), cost, manhattan).unwrap();
+//! assert_eq!(path.cost, 7);
+//! assert_eq!(path.positions.first(), Some(&Position::new(0, 0)));
+//! assert_eq!(path.positions.last(), Some(&Position::new(3, 0)));
+//! ```
+use crate::{Grid, GridView, Neighborhood, Position, Step};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::convert::TryFrom;
+
+/// A path found by a search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path {
+    /// The positions from the start to the goal, both included.
+    pub positions: Vec<Position>,
+    /// The total cost of the path, the cost of the start cell is not included.
+    pub cost: u32,
+}
+
+/// The manhattan distance between `a` and `b`, the number of steps of a 4-connected path.
+/// This is an admissible heuristic for [astar] with a von Neumann neighborhood
+/// and a cost of at least 1 per cell.
+/// # Example
+/// ```
+/// # use gridit::pathfinding::manhattan;
+/// assert_eq!(manhattan((1, 1).into(), (3, 0).into()), 3);
+/// ```
+pub fn manhattan(a: Position, b: Position) -> u32 {
+    saturate(abs_diff(a.x, b.x).saturating_add(abs_diff(a.y, b.y)))
+}
+
+/// The chebyshev distance between `a` and `b`, the number of steps of a 8-connected path.
+/// This is an admissible heuristic for [astar] with a Moore neighborhood
+/// and a cost of at least 1 per cell.
+/// # Example
+/// ```
+/// # use gridit::pathfinding::chebyshev;
+/// assert_eq!(chebyshev((1, 1).into(), (3, 0).into()), 2);
+/// ```
+pub fn chebyshev(a: Position, b: Position) -> u32 {
+    saturate(abs_diff(a.x, b.x).max(abs_diff(a.y, b.y)))
+}
+
+fn abs_diff(a: usize, b: usize) -> usize {
+    a.max(b) - a.min(b)
+}
+
+fn saturate(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+/// Finds the path with the fewest steps from `start` to `goal` with a breadth first search.
+/// The value returned by `cost` is ignored, every passable cell costs 1.
+/// # Example
+/// ```
+/// # use gridit::{Grid, Neighborhood};
+/// # use gridit::pathfinding::bfs;
+/// let grid = Grid::from_str_map("
+/// .#.
+/// ...
+/// ", |c| c).unwrap();
+/// let cost = |cell: &char, _| if *cell == '#' { None } else { Some(1) };
+/// let path = bfs(&grid, (0, 0), (2, 0), &Neighborhood::moore(), cost).unwrap();
+/// assert_eq!(path.positions, vec![(0, 0).into(), (1, 1).into(), (2, 0).into()]);
+/// assert_eq!(path.cost, 2);
+/// ```
+/// # Panics
+/// * if `start` or `goal` is out of bounds.
+pub fn bfs<'a, T: 'a, G, P, F>(
+    grid: G,
+    start: P,
+    goal: P,
+    neighborhood: &Neighborhood,
+    cost: F,
+) -> Option<Path>
+where
+    G: Into<GridView<'a, T>>,
+    P: Into<Position>,
+    F: Fn(&T, Position) -> Option<u32>,
+{
+    let view = grid.into();
+    let (start, goal) = (start.into(), goal.into());
+    assert!(
+        view.is_bounds(start) && view.is_bounds(goal),
+        "start or goal is out of bounds"
+    );
+
+    let mut search = Search::new(view.size());
+    search.visit(start, 0, None);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    while let Some(pos) = queue.pop_front() {
+        if pos == goal {
+            return Some(search.path(goal));
+        }
+        let steps = search.cost(pos).unwrap_or(0).saturating_add(1);
+        for next in view.neighbor_positions(pos, neighborhood) {
+            if search.cost(next).is_some() || cost(view.get_unchecked(next), next).is_none() {
+                continue;
+            }
+            search.visit(next, steps, Some(pos));
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+/// Finds the cheapest path from `start` to `goal` with Dijkstra's algorithm.
+/// # Example
+/// ```
+/// # use gridit::{Grid, Neighborhood};
+/// # use gridit::pathfinding::dijkstra;
+/// // The swamp `~` is expensive to cross
+/// let grid = Grid::from_str_map("
+/// .~.
+/// ...
+/// ", |c| c).unwrap();
+/// let cost = |cell: &char, _| if *cell == '~' { Some(5) } else { Some(1) };
+/// let path = dijkstra(&grid, (0, 0), (2, 0), &Neighborhood::von_neumann(), cost).unwrap();
+/// assert_eq!(path.cost, 4);
+/// ```
+/// # Panics
+/// * if `start` or `goal` is out of bounds.
+pub fn dijkstra<'a, T: 'a, G, P, F>(
+    grid: G,
+    start: P,
+    goal: P,
+    neighborhood: &Neighborhood,
+    cost: F,
+) -> Option<Path>
+where
+    G: Into<GridView<'a, T>>,
+    P: Into<Position>,
+    F: Fn(&T, Position) -> Option<u32>,
+{
+    astar(grid, start, goal, neighborhood, cost, |_, _| 0)
+}
+
+/// Finds the cheapest path from `start` to `goal` with the A* algorithm.
+/// `heuristic` estimates the cost from a position to the goal,
+/// the path is only guaranteed to be the cheapest if it never overestimates.
+/// See [manhattan] and [chebyshev], on wrapping views they can overestimate.
+/// # Example
+/// ```
+/// # use gridit::{Grid, Neighborhood};
+/// # use gridit::pathfinding::{astar, chebyshev};
+/// let grid = Grid::new(5, 5, ());
+/// let path = astar(&grid, (0, 0), (4, 2), &Neighborhood::moore(), |_, _| Some(1), chebyshev).unwrap();
+/// assert_eq!(path.cost, 4);
+/// assert_eq!(path.positions.len(), 5);
+/// ```
+/// # Panics
+/// * if `start` or `goal` is out of bounds.
+pub fn astar<'a, T: 'a, G, P, F, H>(
+    grid: G,
+    start: P,
+    goal: P,
+    neighborhood: &Neighborhood,
+    cost: F,
+    heuristic: H,
+) -> Option<Path>
+where
+    G: Into<GridView<'a, T>>,
+    P: Into<Position>,
+    F: Fn(&T, Position) -> Option<u32>,
+    H: Fn(Position, Position) -> u32,
+{
+    let view = grid.into();
+    let (start, goal) = (start.into(), goal.into());
+    assert!(
+        view.is_bounds(start) && view.is_bounds(goal),
+        "start or goal is out of bounds"
+    );
+
+    let mut search = Search::new(view.size());
+    search.visit(start, 0, None);
+    // Ordered by the estimated total cost, then by the cost so far
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((heuristic(start, goal), 0, search.index(start))));
+    while let Some(Reverse((_, current_cost, idx))) = open.pop() {
+        let pos = search.position(idx);
+        if search.cost(pos) != Some(current_cost) {
+            // A cheaper way to pos was found after this entry was pushed
+            continue;
+        }
+        if pos == goal {
+            return Some(search.path(goal));
+        }
+        for next in view.neighbor_positions(pos, neighborhood) {
+            let next_cost = match cost(view.get_unchecked(next), next)
+                .and_then(|c| current_cost.checked_add(c))
+            {
+                Some(next_cost) => next_cost,
+                None => continue,
+            };
+            if search.cost(next).is_some_and(|c| c <= next_cost) {
+                continue;
+            }
+            search.visit(next, next_cost, Some(pos));
+            let estimate = next_cost.saturating_add(heuristic(next, goal));
+            open.push(Reverse((estimate, next_cost, search.index(next))));
+        }
+    }
+    None
+}
+
+impl<T> Grid<T> {
+    /// Returns the number of steps from every cell to the nearest of `sources`,
+    /// or `None` if no source can be reached from a cell.
+    /// Cells for which `passable` returns false are never entered, sources are always included.
+    /// The steps are those of [Grid::neighbors], see [Grid::distance_field_with] for other neighborhoods.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from_str_map("
+    /// ..#.
+    /// ..#.
+    /// ", |c| c).unwrap();
+    /// let field = grid.distance_field(vec![(0, 0)], |cell, _| *cell != '#');
+    /// assert_eq!(field.row(1).collect::<Vec<_>>(), vec![&Some(1), &Some(1), &None, &None]);
+    /// ```
+    /// # Panics
+    /// * if a source is out of bounds.
+    pub fn distance_field<I, P, F>(&self, sources: I, passable: F) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        self.distance_field_with(sources, &Neighborhood::moore(), passable)
+    }
+
+    /// Returns the number of steps of `neighborhood` from every cell to the nearest of `sources`.
+    /// See [Grid::distance_field].
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood};
+    /// let grid = Grid::new(3, 2, ());
+    /// let field = grid.distance_field_with(vec![(0, 0), (2, 0)], &Neighborhood::von_neumann(), |_, _| true);
+    /// assert_eq!(field, Grid::from(vec![Some(0), Some(1), Some(0), Some(1), Some(2), Some(1)], 3, 2));
+    /// ```
+    /// # Panics
+    /// * if a source is out of bounds.
+    pub fn distance_field_with<I, P, F>(
+        &self,
+        sources: I,
+        neighborhood: &Neighborhood,
+        passable: F,
+    ) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        let search = self.search_from_sources(sources, neighborhood, passable);
+        Grid {
+            items: search.costs,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns for every cell the step to the next cell towards the nearest of `sources`,
+    /// or `None` if no source can be reached from a cell. Sources have a step of (0, 0).
+    /// Cells for which `passable` returns false are never entered.
+    /// The steps are those of [Grid::neighbors], see [Grid::flow_field_with] for other neighborhoods.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Step};
+    /// let grid = Grid::new(3, 1, ());
+    /// let flow = grid.flow_field(vec![(0, 0)], |_, _| true);
+    /// assert_eq!(flow.get((0, 0)), Some(&Some(Step::new(0, 0))));
+    /// assert_eq!(flow.get((2, 0)), Some(&Some(Step::new(-1, 0))));
+    /// ```
+    /// # Panics
+    /// * if a source is out of bounds.
+    pub fn flow_field<I, P, F>(&self, sources: I, passable: F) -> Grid<Option<Step>>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        self.flow_field_with(sources, &Neighborhood::moore(), passable)
+    }
+
+    /// Returns for every cell the step of `neighborhood` to the next cell towards the nearest of `sources`.
+    /// See [Grid::flow_field].
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood, Step};
+    /// let grid = Grid::new(2, 2, ());
+    /// let flow = grid.flow_field_with(vec![(0, 0)], &Neighborhood::von_neumann(), |_, _| true);
+    /// assert_eq!(flow.get((1, 0)), Some(&Some(Step::new(-1, 0))));
+    /// assert_eq!(flow.get((0, 1)), Some(&Some(Step::new(0, -1))));
+    /// ```
+    /// # Panics
+    /// * if a source is out of bounds.
+    pub fn flow_field_with<I, P, F>(
+        &self,
+        sources: I,
+        neighborhood: &Neighborhood,
+        passable: F,
+    ) -> Grid<Option<Step>>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        let search = self.search_from_sources(sources, neighborhood, passable);
+        let items = (0..search.costs.len())
+            .map(|idx| {
+                search.costs[idx]?;
+                let pos = search.position(idx);
+                let next = search.previous[idx].unwrap_or(pos);
+                Some(Step::new(
+                    next.x as isize - pos.x as isize,
+                    next.y as isize - pos.y as isize,
+                ))
+            })
+            .collect();
+        Grid {
+            items,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    // Breadth first search from all sources at once. The search walks the steps of
+    // `neighborhood` backwards, so the previous position of every visited cell
+    // is one step of `neighborhood` closer to a source.
+    fn search_from_sources<I, P, F>(
+        &self,
+        sources: I,
+        neighborhood: &Neighborhood,
+        passable: F,
+    ) -> Search
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        let view = self.as_view();
+        let reversed = Neighborhood::custom(neighborhood.steps().iter().map(|step| -*step));
+        let mut search = Search::new(self.size());
+        let mut queue = VecDeque::new();
+        for source in sources {
+            let source = source.into();
+            self.check_bounds(source)
+                .unwrap_or_else(|e| panic!("{}", e));
+            if search.cost(source).is_none() {
+                search.visit(source, 0, None);
+                queue.push_back(source);
+            }
+        }
+
+        while let Some(pos) = queue.pop_front() {
+            let steps = search.cost(pos).unwrap_or(0).saturating_add(1);
+            for next in view.neighbor_positions(pos, &reversed) {
+                if search.cost(next).is_some() || !passable(view.get_unchecked(next), next) {
+                    continue;
+                }
+                search.visit(next, steps, Some(pos));
+                queue.push_back(next);
+            }
+        }
+        search
+    }
+}
+
+// The cost to reach and the previous position of every visited cell.
+struct Search {
+    width: usize,
+    costs: Vec<Option<u32>>,
+    previous: Vec<Option<Position>>,
+}
+
+impl Search {
+    fn new((width, height): (usize, usize)) -> Self {
+        Self {
+            width,
+            costs: vec![None; width * height],
+            previous: vec![None; width * height],
+        }
+    }
+
+    fn index(&self, pos: Position) -> usize {
+        pos.y * self.width + pos.x
+    }
+
+    fn position(&self, idx: usize) -> Position {
+        (idx % self.width, idx / self.width).into()
+    }
+
+    fn cost(&self, pos: Position) -> Option<u32> {
+        self.costs[self.index(pos)]
+    }
+
+    fn visit(&mut self, pos: Position, cost: u32, previous: Option<Position>) {
+        let idx = self.index(pos);
+        self.costs[idx] = Some(cost);
+        self.previous[idx] = previous;
+    }
+
+    // Follows the previous positions back from the visited position `goal`
+    fn path(&self, goal: Position) -> Path {
+        let mut positions = vec![goal];
+        let mut pos = goal;
+        while let Some(previous) = self.previous[self.index(pos)] {
+            positions.push(previous);
+            pos = previous;
+        }
+        positions.reverse();
+        Path {
+            positions,
+            cost: self.cost(goal).unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Wrap};
+
+    fn positions(v: Vec<(usize, usize)>) -> Vec<Position> {
+        v.into_iter().map(Position::from).collect()
+    }
+
+    // `#` is a wall and `~` a swamp which costs 10
+    fn cost(cell: &char, _: Position) -> Option<u32> {
+        match cell {
+            '#' => None,
+            '~' => Some(10),
+            _ => Some(1),
+        }
+    }
+
+    fn swamp() -> Grid<char> {
+        Grid::from_str_map("S~~~G\n.....", |c| c).unwrap()
+    }
+
+    #[test]
+    fn bfs_ignores_costs() {
+        let path = bfs(&swamp(), (0, 0), (4, 0), &Neighborhood::von_neumann(), cost).unwrap();
+        assert_eq!(
+            path.positions,
+            positions(vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)])
+        );
+        assert_eq!(path.cost, 4);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_avoid_expensive_cells() {
+        let expected = Path {
+            positions: positions(vec![(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (4, 1), (4, 0)]),
+            cost: 6,
+        };
+        let neighborhood = Neighborhood::von_neumann();
+        let grid = swamp();
+        assert_eq!(
+            dijkstra(&grid, (0, 0), (4, 0), &neighborhood, cost),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            astar(&grid, (0, 0), (4, 0), &neighborhood, cost, manhattan),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let grid = Grid::from_str_map("S#G\n.#.", |c| c).unwrap();
+        let moore = Neighborhood::moore();
+        assert_eq!(bfs(&grid, (0, 0), (2, 0), &moore, cost), None);
+        assert_eq!(dijkstra(&grid, (0, 0), (2, 0), &moore, cost), None);
+        assert_eq!(astar(&grid, (0, 0), (2, 0), &moore, cost, chebyshev), None);
+        // an impassable goal can not be reached
+        assert_eq!(bfs(&grid, (0, 0), (1, 0), &moore, cost), None);
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let grid = swamp();
+        let path = dijkstra(&grid, (2, 1), (2, 1), &Neighborhood::moore(), cost).unwrap();
+        assert_eq!(path.positions, positions(vec![(2, 1)]));
+        assert_eq!(path.cost, 0);
+    }
+
+    #[test]
+    fn path_on_wrapping_view() {
+        let grid = Grid::from_str_map("S.#.G", |c| c).unwrap();
+        let view = grid.wrapping(Wrap::Horizontal);
+        let path = bfs(view, (0, 0), (4, 0), &Neighborhood::von_neumann(), cost).unwrap();
+        assert_eq!(path.positions, positions(vec![(0, 0), (4, 0)]));
+        assert_eq!(
+            bfs(&grid, (0, 0), (4, 0), &Neighborhood::moore(), cost),
+            None
+        );
+    }
+
+    #[test]
+    fn distance_field_from_many_sources() {
+        let grid = Grid::from_str_map("S..#.\n.#...\n....S", |c| c).unwrap();
+        let field = grid.distance_field_with(
+            vec![(0, 0), (4, 2)],
+            &Neighborhood::von_neumann(),
+            |cell, _| *cell != '#',
+        );
+        let expected = Grid::from_str_map("012#2\n1#321\n23210", |c| c.to_digit(10)).unwrap();
+        assert_eq!(field, expected);
+    }
+
+    #[test]
+    fn flow_field_leads_to_source() {
+        let grid = Grid::from_str_map(".#.\n.#.\n...", |c| c).unwrap();
+        let passable = |cell: &char, _| *cell != '#';
+        let flow = grid.flow_field_with(vec![(2, 0)], &Neighborhood::von_neumann(), passable);
+        assert_eq!(flow.get((1, 0)), Some(&None));
+
+        // following the flow from any reachable cell ends at the source
+        let mut pos = Position::new(0, 0);
+        let mut steps = 0;
+        while let Some(step) = flow.get(pos).copied().flatten() {
+            if step == Step::new(0, 0) {
+                break;
+            }
+            pos = (pos + step).unwrap();
+            steps += 1;
+        }
+        assert_eq!(pos, Position::new(2, 0));
+        assert_eq!(steps, 6);
+    }
+
+    #[test]
+    fn flow_field_follows_asymmetric_neighborhood() {
+        // Only steps to the right are allowed
+        let right = Neighborhood::custom(vec![(1, 0)]);
+        let grid = Grid::new(3, 1, ());
+        let flow = grid.flow_field_with(vec![(1, 0)], &right, |_, _| true);
+        assert_eq!(
+            flow.items,
+            vec![Some(Step::new(1, 0)), Some(Step::new(0, 0)), None]
+        );
+    }
+}
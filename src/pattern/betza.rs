@@ -0,0 +1,333 @@
+use super::{AnyOf, DirectionPattern, Pattern, Repeat};
+use crate::error::BetzaError;
+use crate::Step;
+use std::str::FromStr;
+
+/// The moves of a fairy chess piece in Betza notation, which compile to patterns.
+///
+/// A piece is a sequence of atoms, every atom can be prefixed by modifiers and followed by a range.
+/// # Atoms
+/// * Leapers in all 8 directions: `W` (1, 0), `F` (1, 1), `D` (2, 0), `N` (1, 2), `A` (2, 2),
+///   `H` (3, 0), `C` (3, 1), `Z` (3, 2) and `G` (3, 3).
+/// * Compounds: `K` = `WF`, `R` = `WW`, `B` = `FF` and `Q` = `WWFF`.
+/// # Ranges
+/// * A single atom leaps once, e.g. `N` is the knight.
+/// * A doubled atom is a rider which slides until the end of the grid, e.g. `NN` is the nightrider.
+/// * A number after the atom limits the slide to that many steps, `0` is unlimited, e.g. `W2`.
+/// # Modifiers
+/// * `m` only moves without capturing, `c` only captures. Without them an atom does both.
+/// * `f` forward, `b` backward, `l` left and `r` right select directions,
+///   `v` vertical is `f` and `b`, `s` sideways is `l` and `r`.
+///   Forward is up (negative y). A direction matches if it matches one of the given
+///   forward, backward and vertical modifiers and one of the given left, right and sideways modifiers,
+///   e.g. `frF` is the forward right diagonal, `vW` the forward and backward step
+///   and `sF` all four diagonals.
+///
+/// Use [Pattern::map_steps] with [Step::reflect_y] for pieces which face down.
+/// # Example
+/// ```
+/// # use gridit::{Grid, PositionsEnumerator};
+/// use gridit::pattern::Betza;
+/// let grid = Grid::from_str_map("
+/// ...
+/// .x.
+/// ...
+/// ", |c| c == 'x').unwrap();
+/// let pawn: Betza = "mfWcfF".parse().unwrap();
+/// let occupied = |cell: &bool, _| *cell;
+///
+/// let moves = grid.pattern((0, 2), pawn.moves()).until_blocked(occupied);
+/// let captures = grid
+///     .pattern((0, 2), pawn.captures())
+///     .until_blocked_inclusive(occupied)
+///     .grid_positions()
+///     .filter(|(_, cell)| **cell)
+///     .map(|(pos, _)| pos);
+/// assert_eq!(moves.count(), 1);
+/// assert_eq!(captures.collect::<Vec<_>>(), vec![(1, 1).into()]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Betza {
+    atoms: Vec<Atom>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Atom {
+    steps: Vec<Step>,
+    repeat: Repeat,
+    moves: bool,
+    captures: bool,
+}
+
+impl Betza {
+    /// Returns the pattern of the cells the piece can move to without capturing.
+    /// Every direction is a segment of the pattern, so it can be blocked with
+    /// [PatternIter::until_blocked](crate::iter::PatternIter::until_blocked).
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::Betza;
+    /// let grid = Grid::new(8, 8, ());
+    /// let queen: Betza = "Q".parse().unwrap();
+    /// assert_eq!(grid.pattern((0, 0), queen.moves()).count(), 21);
+    /// ```
+    pub fn moves(&self) -> AnyOf<DirectionPattern> {
+        self.pattern(|atom| atom.moves)
+    }
+
+    /// Returns the pattern of the cells the piece can capture on.
+    /// Riders can only capture the first piece in their way, so block the pattern with
+    /// [PatternIter::until_blocked_inclusive](crate::iter::PatternIter::until_blocked_inclusive)
+    /// and keep the cells with a piece to capture.
+    pub fn captures(&self) -> AnyOf<DirectionPattern> {
+        self.pattern(|atom| atom.captures)
+    }
+
+    fn pattern<F>(&self, keep: F) -> AnyOf<DirectionPattern>
+    where
+        F: Fn(&Atom) -> bool,
+    {
+        let directions = self
+            .atoms
+            .iter()
+            .filter(|atom| keep(atom))
+            .flat_map(|atom| {
+                atom.steps
+                    .iter()
+                    .map(move |step| DirectionPattern::new(*step, atom.repeat))
+            });
+        Pattern::any_of(directions)
+    }
+}
+
+impl FromStr for Betza {
+    type Err = BetzaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        if chars.is_empty() {
+            return Err(BetzaError::Empty);
+        }
+        let mut atoms = Vec::new();
+        let mut idx = 0;
+        while idx < chars.len() {
+            let mut modifiers = Modifiers::default();
+            while idx < chars.len() && modifiers.add(chars[idx]) {
+                idx += 1;
+            }
+            let c = *chars.get(idx).ok_or(BetzaError::MissingAtom { idx })?;
+            let (bases, mut rider) = atom_steps(c).ok_or(BetzaError::UnknownChar { idx, c })?;
+            let atom_idx = idx;
+            idx += 1;
+            if chars.get(idx) == Some(&c) {
+                rider = true;
+                idx += 1;
+            }
+
+            let range_idx = idx;
+            while chars.get(idx).is_some_and(char::is_ascii_digit) {
+                idx += 1;
+            }
+            let repeat = if range_idx < idx {
+                if rider {
+                    return Err(BetzaError::RangeOnRider { idx: range_idx });
+                }
+                let range: String = chars[range_idx..idx].iter().collect();
+                match range.parse() {
+                    Ok(0) => Repeat::TillEnd,
+                    Ok(n) => Repeat::Times(n),
+                    Err(_) => return Err(BetzaError::InvalidRange { idx: range_idx }),
+                }
+            } else if rider {
+                Repeat::TillEnd
+            } else {
+                Repeat::Once
+            };
+
+            let steps: Vec<Step> = bases
+                .iter()
+                .flat_map(|base| base.symmetries())
+                .filter(|step| modifiers.matches(*step))
+                .collect();
+            if steps.is_empty() {
+                return Err(BetzaError::NoDirection { idx: atom_idx });
+            }
+            atoms.push(Atom {
+                steps,
+                repeat,
+                moves: modifiers.moves || !modifiers.captures,
+                captures: modifiers.captures || !modifiers.moves,
+            });
+        }
+        Ok(Betza { atoms })
+    }
+}
+
+// Returns the base steps of atom `c` and if it is a rider.
+fn atom_steps(c: char) -> Option<(Vec<Step>, bool)> {
+    let w = Step::new(1, 0);
+    let f = Step::new(1, 1);
+    let atom = match c {
+        'W' => (vec![w], false),
+        'F' => (vec![f], false),
+        'D' => (vec![Step::new(2, 0)], false),
+        'N' => (vec![Step::new(1, 2)], false),
+        'A' => (vec![Step::new(2, 2)], false),
+        'H' => (vec![Step::new(3, 0)], false),
+        'C' => (vec![Step::new(3, 1)], false),
+        'Z' => (vec![Step::new(3, 2)], false),
+        'G' => (vec![Step::new(3, 3)], false),
+        'K' => (vec![w, f], false),
+        'R' => (vec![w], true),
+        'B' => (vec![f], true),
+        'Q' => (vec![w, f], true),
+        _ => return None,
+    };
+    Some(atom)
+}
+
+#[derive(Default)]
+struct Modifiers {
+    moves: bool,
+    captures: bool,
+    // forward, backward and vertical
+    vertical: Vec<char>,
+    // left, right and sideways
+    horizontal: Vec<char>,
+}
+
+impl Modifiers {
+    // Adds the modifier `c`, returns false if `c` is no modifier.
+    fn add(&mut self, c: char) -> bool {
+        match c {
+            'm' => self.moves = true,
+            'c' => self.captures = true,
+            'f' | 'b' | 'v' => self.vertical.push(c),
+            'l' | 'r' | 's' => self.horizontal.push(c),
+            _ => return false,
+        }
+        true
+    }
+
+    fn matches(&self, step: Step) -> bool {
+        let vertical = self.vertical.iter().any(|c| match c {
+            'f' => step.y < 0,
+            'b' => step.y > 0,
+            // vertical is forward or backward
+            _ => step.y != 0,
+        });
+        let horizontal = self.horizontal.iter().any(|c| match c {
+            'l' => step.x < 0,
+            'r' => step.x > 0,
+            // sideways is left or right
+            _ => step.x != 0,
+        });
+        (self.vertical.is_empty() || vertical) && (self.horizontal.is_empty() || horizontal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Position, PositionsEnumerator};
+
+    fn sorted(mut positions: Vec<Position>) -> Vec<Position> {
+        positions.sort_by_key(|pos| (pos.y, pos.x));
+        positions
+    }
+
+    fn moves(notation: &str, pos: (usize, usize)) -> Vec<Position> {
+        let grid = Grid::new(8, 8, ());
+        let betza: Betza = notation.parse().unwrap();
+        let moves = grid
+            .pattern(pos, betza.moves())
+            .grid_positions()
+            .map(|(pos, _)| pos)
+            .collect();
+        sorted(moves)
+    }
+
+    #[test]
+    fn parse_standard_pieces() {
+        assert_eq!(moves("N", (4, 4)).len(), 8);
+        assert_eq!(moves("K", (4, 4)).len(), 8);
+        assert_eq!(moves("R", (0, 0)).len(), 14);
+        assert_eq!(moves("B", (0, 0)).len(), 7);
+        assert_eq!(moves("Q", (3, 3)).len(), 27);
+        assert_eq!(moves("Q", (3, 3)), moves("RB", (3, 3)));
+        assert_eq!(moves("WWFF", (3, 3)), moves("RB", (3, 3)));
+        assert_eq!(moves("KK", (3, 3)), moves("Q", (3, 3)));
+        // the amazon moves like a queen and a knight
+        assert_eq!(moves("QN", (3, 3)).len(), 35);
+    }
+
+    #[test]
+    fn parse_ranges_and_riders() {
+        assert_eq!(
+            moves("W2", (0, 0)),
+            vec![(1, 0).into(), (2, 0).into(), (0, 1).into(), (0, 2).into()]
+        );
+        assert_eq!(moves("W0", (0, 0)), moves("R", (0, 0)));
+        assert_eq!(moves("F12", (0, 0)), moves("B", (0, 0)));
+        assert_eq!(
+            moves("NN", (0, 0)),
+            sorted(vec![
+                (2, 1).into(),
+                (4, 2).into(),
+                (6, 3).into(),
+                (1, 2).into(),
+                (2, 4).into(),
+                (3, 6).into()
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_modifiers() {
+        assert_eq!(moves("fW", (3, 3)), vec![(3, 2).into()]);
+        assert_eq!(moves("fR", (3, 3)).len(), 3);
+        assert_eq!(moves("sW", (3, 3)), vec![(2, 3).into(), (4, 3).into()]);
+        assert_eq!(moves("vW", (3, 3)), vec![(3, 2).into(), (3, 4).into()]);
+        assert_eq!(moves("sF", (3, 3)), moves("F", (3, 3)));
+        assert_eq!(moves("vF", (3, 3)), moves("F", (3, 3)));
+        assert_eq!(moves("frF", (3, 3)), vec![(4, 2).into()]);
+        assert_eq!(moves("fN", (3, 3)).len(), 4);
+        assert_eq!(
+            moves("fsN", (3, 3)),
+            vec![(2, 1).into(), (4, 1).into(), (1, 2).into(), (5, 2).into()]
+        );
+        assert_eq!(moves("vN", (3, 3)), moves("N", (3, 3)));
+        assert_eq!(moves("sN", (3, 3)), moves("N", (3, 3)));
+
+        let pawn: Betza = "mfWcfF".parse().unwrap();
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(grid.pattern((1, 2), pawn.moves()).count(), 1);
+        assert_eq!(grid.pattern((1, 2), pawn.captures()).count(), 2);
+        let king: Betza = "mcK".parse().unwrap();
+        assert_eq!(king.moves(), king.captures());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse = |s: &str| s.parse::<Betza>();
+        assert_eq!(parse(""), Err(BetzaError::Empty));
+        assert_eq!(parse("Wx"), Err(BetzaError::UnknownChar { idx: 1, c: 'x' }));
+        assert_eq!(
+            parse("W 2"),
+            Err(BetzaError::UnknownChar { idx: 1, c: ' ' })
+        );
+        assert_eq!(parse("2W"), Err(BetzaError::UnknownChar { idx: 0, c: '2' }));
+        assert_eq!(parse("Nmf"), Err(BetzaError::MissingAtom { idx: 3 }));
+        assert_eq!(parse("R2"), Err(BetzaError::RangeOnRider { idx: 1 }));
+        assert_eq!(parse("WW3"), Err(BetzaError::RangeOnRider { idx: 2 }));
+        assert_eq!(
+            parse("W99999999999999999999999"),
+            Err(BetzaError::InvalidRange { idx: 1 })
+        );
+        assert_eq!(parse("NflW"), Err(BetzaError::NoDirection { idx: 3 }));
+        assert_eq!(
+            parse("Wx").unwrap_err().to_string(),
+            "unknown char 'x' at 1, expected an atom or a modifier"
+        );
+    }
+}
@@ -0,0 +1,440 @@
+use super::{Action, Pattern, Repeat};
+use crate::Step;
+
+/// Runs the segments of one pattern and then the segments of another,
+/// created by [Pattern::chain].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+    in_second: bool,
+    // If the current segment is the first segment of the second pattern
+    second_starts: bool,
+}
+
+impl<A, B> Chain<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            in_second: false,
+            second_starts: false,
+        }
+    }
+}
+
+impl<A: Pattern, B: Pattern> Pattern for Chain<A, B> {
+    fn next_action(&mut self) -> Option<Action> {
+        if self.in_second {
+            return self.second.next_action();
+        }
+        self.first.next_action()
+    }
+
+    fn repeat(&self) -> &Repeat {
+        if self.in_second {
+            return self.second.repeat();
+        }
+        self.first.repeat()
+    }
+
+    fn next_segment(&mut self) -> bool {
+        if self.in_second {
+            self.second_starts = false;
+            return self.second.next_segment();
+        }
+        if !self.first.next_segment() {
+            self.in_second = true;
+            self.second_starts = true;
+        }
+        true
+    }
+
+    fn distinct(&self) -> bool {
+        if self.in_second {
+            return self.second.distinct();
+        }
+        self.first.distinct()
+    }
+
+    fn new_group(&self) -> bool {
+        if self.in_second {
+            return self.second_starts || self.second.new_group();
+        }
+        self.first.new_group()
+    }
+}
+
+/// The union of patterns, which yields every cell only once,
+/// created by [Pattern::any_of]. All segments of the patterns are in one group.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnyOf<P> {
+    patterns: Vec<P>,
+    idx: usize,
+}
+
+impl<P> AnyOf<P> {
+    pub(crate) fn new<I>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+    {
+        Self {
+            patterns: patterns.into_iter().collect(),
+            idx: 0,
+        }
+    }
+}
+
+impl<P: Pattern> Pattern for AnyOf<P> {
+    fn next_action(&mut self) -> Option<Action> {
+        self.patterns.get_mut(self.idx)?.next_action()
+    }
+
+    fn repeat(&self) -> &Repeat {
+        match self.patterns.get(self.idx) {
+            Some(pattern) => pattern.repeat(),
+            None => &Repeat::TillEnd,
+        }
+    }
+
+    fn next_segment(&mut self) -> bool {
+        let pattern = match self.patterns.get_mut(self.idx) {
+            Some(pattern) => pattern,
+            None => return false,
+        };
+        if pattern.next_segment() {
+            return true;
+        }
+        self.idx += 1;
+        self.idx < self.patterns.len()
+    }
+
+    fn distinct(&self) -> bool {
+        true
+    }
+}
+
+/// The union of the images of a pattern under some symmetry,
+/// created by [Pattern::mirrored_x] and [Pattern::rotations4].
+pub type Symmetric<P> = AnyOf<MapSteps<P, fn(Step) -> Step>>;
+
+/// Runs every segment of a pattern with another `Repeat`, created by [Pattern::repeated].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repeated<P> {
+    pattern: P,
+    repeat: Repeat,
+}
+
+impl<P> Repeated<P> {
+    pub(crate) fn new(pattern: P, repeat: Repeat) -> Self {
+        Self { pattern, repeat }
+    }
+}
+
+impl<P: Pattern> Pattern for Repeated<P> {
+    fn next_action(&mut self) -> Option<Action> {
+        self.pattern.next_action()
+    }
+
+    fn repeat(&self) -> &Repeat {
+        &self.repeat
+    }
+
+    fn next_segment(&mut self) -> bool {
+        self.pattern.next_segment()
+    }
+
+    fn distinct(&self) -> bool {
+        self.pattern.distinct()
+    }
+
+    fn new_group(&self) -> bool {
+        self.pattern.new_group()
+    }
+}
+
+/// Maps the steps and side steps of a pattern, created by [Pattern::map_steps].
+#[derive(Clone, Debug)]
+pub struct MapSteps<P, F> {
+    pattern: P,
+    f: F,
+}
+
+impl<P, F> MapSteps<P, F> {
+    pub(crate) fn new(pattern: P, f: F) -> Self {
+        Self { pattern, f }
+    }
+}
+
+impl<P, F> Pattern for MapSteps<P, F>
+where
+    P: Pattern,
+    F: Fn(Step) -> Step,
+{
+    fn next_action(&mut self) -> Option<Action> {
+        let action = match self.pattern.next_action()? {
+            Action::Step(step) => Action::Step((self.f)(step)),
+            Action::StepFromOrigin(step) => Action::StepFromOrigin((self.f)(step)),
+            action => action,
+        };
+        Some(action)
+    }
+
+    fn repeat(&self) -> &Repeat {
+        self.pattern.repeat()
+    }
+
+    fn next_segment(&mut self) -> bool {
+        self.pattern.next_segment()
+    }
+
+    fn distinct(&self) -> bool {
+        self.pattern.distinct()
+    }
+
+    fn new_group(&self) -> bool {
+        self.pattern.new_group()
+    }
+}
+
+/// Filters the steps and side steps of a pattern with a predicate,
+/// created by [Pattern::filter_steps].
+#[derive(Clone, Debug)]
+pub struct FilterSteps<P, F> {
+    pattern: P,
+    predicate: F,
+}
+
+impl<P, F> FilterSteps<P, F> {
+    pub(crate) fn new(pattern: P, predicate: F) -> Self {
+        Self { pattern, predicate }
+    }
+}
+
+impl<P, F> Pattern for FilterSteps<P, F>
+where
+    P: Pattern,
+    F: Fn(Step) -> bool,
+{
+    fn next_action(&mut self) -> Option<Action> {
+        loop {
+            match self.pattern.next_action()? {
+                // The walk can not go on without the step, so the segment ends
+                Action::Step(step) if !(self.predicate)(step) => return None,
+                Action::StepFromOrigin(step) if !(self.predicate)(step) => {}
+                action => return Some(action),
+            }
+        }
+    }
+
+    fn repeat(&self) -> &Repeat {
+        self.pattern.repeat()
+    }
+
+    fn next_segment(&mut self) -> bool {
+        self.pattern.next_segment()
+    }
+
+    fn distinct(&self) -> bool {
+        self.pattern.distinct()
+    }
+
+    fn new_group(&self) -> bool {
+        self.pattern.new_group()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::{DirectionPattern, JumpsPattern, SideStepsPattern, StepsPattern};
+    use crate::{Grid, Position, PositionsEnumerator, Wrap};
+
+    fn positions<P: Pattern + 'static>(
+        grid: &Grid<usize>,
+        pos: (usize, usize),
+        pattern: P,
+    ) -> Vec<Position> {
+        grid.pattern(pos, pattern)
+            .grid_positions()
+            .map(|(pos, cell)| {
+                assert_eq!(*cell, pos.y * grid.width + pos.x);
+                pos
+            })
+            .collect()
+    }
+
+    // 0, 1, 2, 3
+    // 4, 5, 6, 7
+    // 8, 9,10,11
+    //12,13,14,15
+    fn grid() -> Grid<usize> {
+        Grid {
+            width: 4,
+            height: 4,
+            items: (0..16).collect(),
+        }
+    }
+
+    #[test]
+    fn chain_starts_segments_at_origin() {
+        let grid = grid();
+        let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd)
+            .chain(StepsPattern::new(vec![(0, 1), (0, 1)]))
+            .chain(JumpsPattern::new(vec![(3, 3)]));
+        assert_eq!(
+            positions(&grid, (1, 1), pattern),
+            vec![
+                (2, 1).into(),
+                (3, 1).into(),
+                (1, 2).into(),
+                (1, 3).into(),
+                (3, 3).into()
+            ]
+        );
+
+        // Cells are yielded by every pattern which reaches them
+        let ray = DirectionPattern::new((0, 1), Repeat::TillEnd);
+        assert_eq!(positions(&grid, (0, 2), ray.chain(ray)).len(), 2);
+    }
+
+    #[test]
+    fn chain_dedups_only_inside_any_of() {
+        let grid = grid();
+        let east = DirectionPattern::new((1, 0), Repeat::TillEnd);
+        let rays = east.rotations4();
+        let row: Vec<Position> = vec![(1, 0).into(), (2, 0).into(), (3, 0).into()];
+        let column: Vec<Position> = vec![(0, 1).into(), (0, 2).into(), (0, 3).into()];
+        let rays_cells = [row.clone(), column].concat();
+
+        assert_eq!(
+            positions(&grid, (0, 0), east.chain(rays.clone())),
+            [row.clone(), rays_cells.clone()].concat()
+        );
+        assert_eq!(
+            positions(&grid, (0, 0), rays.clone().chain(east)),
+            [rays_cells.clone(), row].concat()
+        );
+        assert_eq!(
+            positions(&grid, (0, 0), rays.clone().chain(rays.clone())),
+            [rays_cells.clone(), rays_cells.clone()].concat()
+        );
+
+        // A chain inside a union is deduplicated as a whole
+        let union = Pattern::any_of(vec![east.chain(rays)]);
+        assert_eq!(positions(&grid, (0, 0), union), rays_cells);
+    }
+
+    #[test]
+    fn any_of_yields_every_cell_once() {
+        let grid = grid();
+        let patterns: Vec<Box<dyn Pattern>> = vec![
+            Box::new(DirectionPattern::new((1, 0), Repeat::TillEnd)),
+            Box::new(DirectionPattern::new((2, 0), Repeat::TillEnd)),
+            Box::new(SideStepsPattern::new(vec![(1, 0), (0, 1)])),
+        ];
+        assert_eq!(
+            positions(&grid, (0, 0), Pattern::any_of(patterns)),
+            vec![(1, 0).into(), (2, 0).into(), (3, 0).into(), (0, 1).into()]
+        );
+
+        let empty: Vec<DirectionPattern> = Vec::new();
+        assert_eq!(positions(&grid, (0, 0), Pattern::any_of(empty)), vec![]);
+    }
+
+    #[test]
+    fn queen_is_one_pattern() {
+        let grid = grid();
+        let rook = DirectionPattern::new((1, 0), Repeat::TillEnd).rotations4();
+        let bishop = DirectionPattern::new((1, 1), Repeat::TillEnd).rotations4();
+        let queen = rook.chain(bishop);
+
+        let mut moves = positions(&grid, (1, 1), queen);
+        assert_eq!(moves.len(), 6 + 5);
+        moves.sort_by_key(|pos| (pos.y, pos.x));
+        let expected: Vec<Position> = grid
+            .positions()
+            .filter(|pos| pos.x == 1 || pos.y == 1 || pos.x == pos.y || pos.x + pos.y == 2)
+            .filter(|pos| *pos != (1, 1).into())
+            .collect();
+        assert_eq!(moves, expected);
+    }
+
+    #[test]
+    fn repeated_limits_every_segment() {
+        let grid = grid();
+        let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd)
+            .rotations4()
+            .repeated(Repeat::Times(2));
+        assert_eq!(
+            positions(&grid, (1, 1), pattern),
+            vec![
+                (2, 1).into(),
+                (3, 1).into(),
+                (1, 2).into(),
+                (1, 3).into(),
+                (0, 1).into(),
+                (1, 0).into()
+            ]
+        );
+
+        // A repeated ray on a wrapping grid still ends before the origin
+        let pattern = DirectionPattern::new((1, 0), Repeat::Once).repeated(Repeat::TillEnd);
+        let iter = grid.wrapping(Wrap::Both).pattern((1, 0), pattern);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3, &0]);
+    }
+
+    #[test]
+    fn mirrored_and_rotated_steps() {
+        let grid = grid();
+        let pattern = StepsPattern::new(vec![(1, 0), (1, 1)]).mirrored_x();
+        assert_eq!(
+            positions(&grid, (1, 0), pattern),
+            vec![(2, 0).into(), (3, 1).into(), (0, 0).into()]
+        );
+
+        let knight = SideStepsPattern::new(vec![(1, 2), (2, 1)]).rotations4();
+        assert_eq!(
+            positions(&grid, (0, 0), knight),
+            vec![(1, 2).into(), (2, 1).into()]
+        );
+
+        // Jumps are not transformed, so the rotations only yield them once
+        let pattern = JumpsPattern::new(vec![(3, 3)]).rotations4();
+        assert_eq!(positions(&grid, (0, 0), pattern), vec![(3, 3).into()]);
+    }
+
+    #[test]
+    fn filter_steps_skips_side_steps_and_ends_walks() {
+        let grid = grid();
+        let king = SideStepsPattern::new(vec![(1, 0), (1, 1)]).rotations4();
+        let forward = king.filter_steps(|step| step.y < 0);
+        assert_eq!(
+            positions(&grid, (1, 1), forward),
+            vec![(0, 0).into(), (1, 0).into(), (2, 0).into()]
+        );
+
+        let rays = DirectionPattern::new((0, 1), Repeat::TillEnd)
+            .rotations4()
+            .filter_steps(|step| step.x >= 0);
+        assert_eq!(
+            positions(&grid, (2, 2), rays),
+            vec![(2, 3).into(), (2, 1).into(), (2, 0).into(), (3, 2).into()]
+        );
+
+        let walk = StepsPattern::new(vec![(1, 0), (0, 1), (1, 0)]).filter_steps(|step| step.y == 0);
+        assert_eq!(positions(&grid, (0, 0), walk), vec![(1, 0).into()]);
+    }
+
+    #[test]
+    fn map_steps_and_pattern_mut() {
+        let mut grid = grid();
+        let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd)
+            .rotations4()
+            .map_steps(|step| step * 2);
+        grid.pattern_mut((1, 1), pattern).for_each(|cell| *cell = 0);
+        assert_eq!(grid.items.iter().filter(|cell| **cell == 0).count(), 3);
+        assert_eq!(grid[(3, 1)], 0);
+        assert_eq!(grid[(1, 3)], 0);
+    }
+}
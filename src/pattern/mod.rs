@@ -0,0 +1,595 @@
+//! All patterns and Pattern Trait used for [pattern](crate::Grid::pattern).
+use crate::{Position, Step, Symmetry};
+
+mod betza;
+mod combinator;
+
+pub use betza::Betza;
+pub use combinator::{AnyOf, Chain, FilterSteps, MapSteps, Repeated, Symmetric};
+
+/// This trait is there to create pattern for the [PatternIter](crate::iter::PatternIter).
+/// A pattern can mix all variants of `Action`, e.g. jump to a position and step on from there.
+/// # Variants
+/// * `Action::Step(step)` steps from the previous position, the segment ends if `step` leads outside the grid.
+/// * `Action::StepFromOrigin(step)` if `step` steps outside the grid, this Action will be ignored and next_action will be called again.
+/// * `Action::Jump(position)` if `position` is outside the grid, this Action will be ignored and next_action will be called again.
+///
+/// Skipped side steps and jumps do not end the segment, so a pattern whose `next_action`
+/// never returns None must not keep returning side steps or jumps outside the grid,
+/// otherwise the iterator never returns. Endless patterns should walk with `Action::Step`,
+/// which ends the segment at the edge of the grid.
+///
+/// Only the actions which lead to a cell count for the `Repeat` of the pattern.
+/// # Segments
+/// A pattern can consist of multiple segments, e.g. the rays of a queen.
+/// A segment ends when a step leads outside the grid, its `Repeat` is reached or
+/// `next_action` returns None. Then [Pattern::next_segment] is called and the next
+/// segment starts again at the original position.
+/// # Combinators
+/// Patterns can be combined into a single pattern, e.g. the moves of a queen:
+/// ```
+/// # use gridit::Grid;
+/// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+/// let rook = DirectionPattern::new((1, 0), Repeat::TillEnd).rotations4();
+/// let bishop = DirectionPattern::new((1, 1), Repeat::TillEnd).rotations4();
+/// let queen = Pattern::any_of(vec![rook, bishop]);
+///
+/// let grid = Grid::new(8, 8, ());
+/// assert_eq!(grid.pattern((0, 0), queen).count(), 21);
+/// ```
+pub trait Pattern {
+    /// Returns the next `Action` or None if there are no more `Action`.
+    fn next_action(&mut self) -> Option<Action>;
+
+    /// Returns a reference to the `Repeat`.
+    fn repeat(&self) -> &Repeat;
+
+    /// Starts the next segment and returns true, or returns false if there are no more segments.
+    /// By default a pattern has only one segment.
+    fn next_segment(&mut self) -> bool {
+        false
+    }
+
+    /// Returns true if the current segment should only yield the cells which were not yielded
+    /// before in its group of segments. Defaults to false, unions like [Pattern::any_of] return true.
+    fn distinct(&self) -> bool {
+        false
+    }
+
+    /// Returns true if the segment started by [Pattern::next_segment] starts a new group,
+    /// e.g. the second pattern of [Pattern::chain]. The cells of the previous groups
+    /// are yielded again. Defaults to false, all segments are in one group.
+    fn new_group(&self) -> bool {
+        false
+    }
+
+    /// Creates a pattern which runs the segments of `self` and then the segments of `other`.
+    /// Cells which are reached by both patterns are yielded twice.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd)
+    ///     .chain(DirectionPattern::new((0, 1), Repeat::Once));
+    /// let cells: Vec<_> = grid.pattern((0, 0), pattern).collect();
+    /// assert_eq!(cells, vec![&1, &2, &3]);
+    /// ```
+    fn chain<B>(self, other: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+        B: Pattern,
+    {
+        Chain::new(self, other)
+    }
+
+    /// Creates a pattern which is the union of `patterns`. The patterns are run one
+    /// after another, but every cell is only yielded once.
+    /// Use `Box<dyn Pattern>` to combine patterns of different types.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat, SideStepsPattern};
+    /// let grid = Grid::new(3, 3, ());
+    /// let patterns: Vec<Box<dyn Pattern>> = vec![
+    ///     Box::new(DirectionPattern::new((1, 0), Repeat::TillEnd)),
+    ///     Box::new(SideStepsPattern::new(vec![(2, 0), (0, 1)])),
+    /// ];
+    /// let pattern = Pattern::any_of(patterns);
+    /// // (2, 0) is reached by both patterns
+    /// assert_eq!(grid.pattern((0, 0), pattern).count(), 3);
+    /// ```
+    fn any_of<I>(patterns: I) -> AnyOf<Self>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Self>,
+    {
+        AnyOf::new(patterns)
+    }
+
+    /// Creates a pattern which runs every segment of `self` with `repeat`.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+    /// let grid = Grid::new(8, 8, ());
+    /// let king = DirectionPattern::new((1, 0), Repeat::TillEnd)
+    ///     .rotations4()
+    ///     .repeated(Repeat::Once);
+    /// assert_eq!(grid.pattern((4, 4), king).count(), 4);
+    /// ```
+    fn repeated(self, repeat: Repeat) -> Repeated<Self>
+    where
+        Self: Sized,
+    {
+        Repeated::new(self, repeat)
+    }
+
+    /// Creates a pattern which maps every step and side step of `self` with `f`.
+    /// Jumps are not changed.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Step};
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd).map_steps(|step| step * 2);
+    /// assert_eq!(grid.pattern((0, 0), pattern).collect::<Vec<_>>(), vec![&2]);
+    /// ```
+    fn map_steps<F>(self, f: F) -> MapSteps<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Step) -> Step,
+    {
+        MapSteps::new(self, f)
+    }
+
+    /// Creates a pattern which filters the steps and side steps of `self` with `predicate`.
+    /// Side steps for which `predicate` returns false are skipped, a step for which
+    /// `predicate` returns false ends the current segment. Jumps are not filtered.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{Pattern, SideStepsPattern};
+    /// let grid = Grid::new(5, 5, ());
+    /// let knight = SideStepsPattern::new(vec![(1, 2), (2, 1)]).rotations4();
+    /// // only the moves forward
+    /// let forward = knight.filter_steps(|step| step.y < 0);
+    /// assert_eq!(grid.pattern((2, 2), forward).count(), 4);
+    /// ```
+    fn filter_steps<F>(self, predicate: F) -> FilterSteps<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Step) -> bool,
+    {
+        FilterSteps::new(self, predicate)
+    }
+
+    /// Creates the union of the images of `self` under `symmetry`, which transforms
+    /// every step and side step. Jumps are not transformed.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Symmetry};
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+    /// let grid = Grid::new(8, 8, ());
+    /// let bishop = DirectionPattern::new((1, 1), Repeat::TillEnd).symmetric(Symmetry::Rotations4);
+    /// assert_eq!(grid.pattern((0, 0), bishop).count(), 7);
+    /// ```
+    fn symmetric(self, symmetry: Symmetry) -> Symmetric<Self>
+    where
+        Self: Sized + Clone,
+    {
+        let transforms = symmetry.transforms();
+        Pattern::any_of(transforms.into_iter().map(|f| self.clone().map_steps(f)))
+    }
+
+    /// Creates the union of `self` and `self` mirrored with [Step::reflect_x],
+    /// which negates the x of every step and side step. Jumps are not mirrored.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{Pattern, SideStepsPattern};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let pattern = SideStepsPattern::new(vec![(1, 1)]).mirrored_x();
+    /// assert_eq!(grid.pattern((1, 1), pattern).collect::<Vec<_>>(), vec![&8, &6]);
+    /// ```
+    fn mirrored_x(self) -> Symmetric<Self>
+    where
+        Self: Sized + Clone,
+    {
+        self.symmetric(Symmetry::MirrorX)
+    }
+
+    /// Creates the union of `self` rotated by 0, 90, 180 and 270 degrees clockwise
+    /// with [Step::rotate90], which rotates every step and side step. Jumps are not rotated.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// use gridit::pattern::{DirectionPattern, Pattern, Repeat};
+    /// let grid = Grid::new(8, 8, ());
+    /// let rook = DirectionPattern::new((0, -1), Repeat::TillEnd).rotations4();
+    /// assert_eq!(grid.pattern((0, 0), rook).count(), 14);
+    /// ```
+    fn rotations4(self) -> Symmetric<Self>
+    where
+        Self: Sized + Clone,
+    {
+        self.symmetric(Symmetry::Rotations4)
+    }
+}
+
+impl<P: Pattern + ?Sized> Pattern for Box<P> {
+    fn next_action(&mut self) -> Option<Action> {
+        (**self).next_action()
+    }
+
+    fn repeat(&self) -> &Repeat {
+        (**self).repeat()
+    }
+
+    fn next_segment(&mut self) -> bool {
+        (**self).next_segment()
+    }
+
+    fn distinct(&self) -> bool {
+        (**self).distinct()
+    }
+
+    fn new_group(&self) -> bool {
+        (**self).new_group()
+    }
+}
+
+/// Movement action to perform.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    /// Steps to next position from the previous one.
+    // Step from previous position
+    Step(Step),
+
+    /// Steps to the next position from the original position provided.
+    /// The original position does stay the same.
+    // Step from origin position, Steps which do not reach into the grid will be ignored
+    StepFromOrigin(Step),
+
+    /// Does jump to the position. No previous or original position are are considered.
+    // Jump to any position
+    Jump(Position),
+}
+
+/// How often a pattern is run.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Repeat {
+    Once,
+    TillEnd,
+    Times(usize),
+}
+
+/// Steps in only one direction until end or grid or the repeat condition is meet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectionPattern {
+    pub(crate) step: Step,
+    pub(crate) repeat: Repeat,
+}
+
+impl DirectionPattern {
+    pub fn new<S: Into<Step>>(step: S, repeat: Repeat) -> Self {
+        Self {
+            step: step.into(),
+            repeat,
+        }
+    }
+}
+
+impl Pattern for DirectionPattern {
+    fn next_action(&mut self) -> Option<Action> {
+        Some(Action::Step(self.step))
+    }
+
+    fn repeat(&self) -> &Repeat {
+        &self.repeat
+    }
+}
+
+/// Walks the steps given, until one step leads outside the grid.
+/// Created with a symmetry, every unique image of the walk is walked
+/// on its own from the original position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StepsPattern {
+    // The steps of all walks, every walk has walk_len steps
+    pub(crate) steps: Vec<Step>,
+    pub(crate) walk_len: usize,
+    pub(crate) walk: usize,
+    pub(crate) idx: usize,
+}
+
+impl StepsPattern {
+    pub fn new<T: Copy + Into<Step>>(steps: Vec<T>) -> Self {
+        Self::with_symmetry(steps, Symmetry::Identity)
+    }
+
+    /// Creates a pattern which walks every unique image of `steps` under `symmetry`.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Symmetry};
+    /// # use gridit::pattern::StepsPattern;
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// // one step forward, then one step to the right or the left
+    /// let pattern = StepsPattern::with_symmetry(vec![(0, -1), (1, 0)], Symmetry::MirrorX);
+    /// let cells: Vec<_> = grid.pattern((1, 2), pattern).collect();
+    /// assert_eq!(cells, vec![&4, &5, &4, &3]);
+    /// ```
+    pub fn with_symmetry<T: Copy + Into<Step>>(steps: Vec<T>, symmetry: Symmetry) -> Self {
+        let steps: Vec<Step> = steps.iter().map(|t| (*t).into()).collect();
+        let mut walks: Vec<Vec<Step>> = Vec::new();
+        for transform in symmetry.transforms() {
+            let walk = steps.iter().map(|step| transform(*step)).collect();
+            if !walks.contains(&walk) {
+                walks.push(walk);
+            }
+        }
+        Self {
+            steps: walks.concat(),
+            walk_len: steps.len(),
+            walk: 0,
+            idx: 0,
+        }
+    }
+}
+
+// Unvalidated pattern data, checked on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "StepsPattern")]
+struct RawStepsPattern {
+    steps: Vec<Step>,
+    walk_len: usize,
+    walk: usize,
+    idx: usize,
+}
+
+/// Deserializes the pattern and returns an error
+/// if the walk and step indices do not match the steps.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StepsPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = RawStepsPattern::deserialize(deserializer)?;
+        let valid = match raw.steps.len().checked_div(raw.walk_len) {
+            // Without steps there is only the empty walk
+            None => raw.steps.is_empty() && raw.walk == 0 && raw.idx == 0,
+            Some(walks) => {
+                raw.steps.len() % raw.walk_len == 0 && raw.walk < walks && raw.idx <= raw.walk_len
+            }
+        };
+        if !valid {
+            return Err(serde::de::Error::custom(format!(
+                "walk {} at step {} with walks of length {} does not match {} steps",
+                raw.walk,
+                raw.idx,
+                raw.walk_len,
+                raw.steps.len()
+            )));
+        }
+        Ok(Self {
+            steps: raw.steps,
+            walk_len: raw.walk_len,
+            walk: raw.walk,
+            idx: raw.idx,
+        })
+    }
+}
+
+impl Pattern for StepsPattern {
+    fn next_action(&mut self) -> Option<Action> {
+        if self.idx >= self.walk_len {
+            return None;
+        }
+        self.idx += 1;
+        let step = self.steps[self.walk * self.walk_len + self.idx - 1];
+        Some(Action::Step(step))
+    }
+
+    fn repeat(&self) -> &Repeat {
+        &Repeat::TillEnd
+    }
+
+    fn next_segment(&mut self) -> bool {
+        if (self.walk + 1) * self.walk_len >= self.steps.len() {
+            return false;
+        }
+        self.walk += 1;
+        self.idx = 0;
+        true
+    }
+}
+
+/// A pattern which side steps from the original position.
+/// Steps which lead outside the grid are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SideStepsPattern {
+    pub(crate) steps: Vec<Step>,
+    pub(crate) idx: usize,
+}
+
+impl SideStepsPattern {
+    pub fn new<I>(steps: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Copy + Into<Step>,
+    {
+        Self {
+            steps: steps.into_iter().map(|t| t.into()).collect(),
+            idx: 0,
+        }
+    }
+
+    /// Creates a pattern which side steps to every unique image of `steps` under `symmetry`,
+    /// e.g. a knight from a single offset.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Symmetry};
+    /// # use gridit::pattern::SideStepsPattern;
+    /// let grid = Grid::new(5, 5, ());
+    /// let knight = SideStepsPattern::with_symmetry(vec![(1, 2)], Symmetry::All);
+    /// assert_eq!(grid.pattern((2, 2), knight).count(), 8);
+    /// ```
+    pub fn with_symmetry<I>(steps: I, symmetry: Symmetry) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Copy + Into<Step>,
+    {
+        let mut images = Vec::new();
+        for step in steps {
+            for image in step.into().images(symmetry) {
+                if !images.contains(&image) {
+                    images.push(image);
+                }
+            }
+        }
+        Self {
+            steps: images,
+            idx: 0,
+        }
+    }
+}
+
+impl Pattern for SideStepsPattern {
+    fn next_action(&mut self) -> Option<Action> {
+        let step = *self.steps.get(self.idx)?;
+        self.idx += 1;
+        Some(Action::StepFromOrigin(step))
+    }
+
+    fn repeat(&self) -> &Repeat {
+        &Repeat::TillEnd
+    }
+}
+
+/// A pattern which jumps to the given positions.
+/// Positions outside the grid are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JumpsPattern {
+    jumps: Vec<Position>,
+    idx: usize,
+}
+
+impl JumpsPattern {
+    pub fn new<I>(positions: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Copy + Into<Position>,
+    {
+        Self {
+            jumps: positions.into_iter().map(|t| t.into()).collect(),
+            idx: 0,
+        }
+    }
+}
+
+impl Pattern for JumpsPattern {
+    fn next_action(&mut self) -> Option<Action> {
+        let pos = *self.jumps.get(self.idx)?;
+        self.idx += 1;
+        Some(Action::Jump(pos))
+    }
+
+    fn repeat(&self) -> &Repeat {
+        &Repeat::TillEnd
+    }
+}
+
+/// A pattern which performs the given actions one after another,
+/// which can be any mix of steps, side steps and jumps.
+/// # Example
+/// ```
+/// # use gridit::Grid;
+/// # use gridit::pattern::{Action, ActionsPattern};
+/// let grid = Grid::from((0..9).collect(), 3, 3);
+/// // slide two cells to the right, then teleport and step down
+/// let pattern = ActionsPattern::new(vec![
+///     Action::Step((1, 0).into()),
+///     Action::Step((1, 0).into()),
+///     Action::Jump((0, 1).into()),
+///     Action::Step((0, 1).into()),
+/// ]);
+/// let cells: Vec<_> = grid.pattern((0, 0), pattern).collect();
+/// assert_eq!(cells, vec![&1, &2, &3, &6]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionsPattern {
+    actions: Vec<Action>,
+    idx: usize,
+}
+
+impl ActionsPattern {
+    pub fn new<I>(actions: I) -> Self
+    where
+        I: IntoIterator<Item = Action>,
+    {
+        Self {
+            actions: actions.into_iter().collect(),
+            idx: 0,
+        }
+    }
+}
+
+impl Pattern for ActionsPattern {
+    fn next_action(&mut self) -> Option<Action> {
+        let action = *self.actions.get(self.idx)?;
+        self.idx += 1;
+        Some(action)
+    }
+
+    fn repeat(&self) -> &Repeat {
+        &Repeat::TillEnd
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn serde_steps_pattern_checks_walks() {
+        let pattern = StepsPattern::with_symmetry(vec![(1, 0), (1, 0)], Symmetry::MirrorX);
+        let json = serde_json::to_string(&pattern).unwrap();
+        let mut de: StepsPattern = serde_json::from_str(&json).unwrap();
+        assert_eq!(de, pattern);
+        assert_eq!(de.next_action(), Some(Action::Step((1, 0).into())));
+
+        let step = r#"{"x":1,"y":0}"#;
+        let invalid = [
+            format!(
+                r#"{{"steps":[{0},{0},{0}],"walk_len":2,"walk":0,"idx":0}}"#,
+                step
+            ),
+            format!(
+                r#"{{"steps":[{0},{0}],"walk_len":2,"walk":1,"idx":0}}"#,
+                step
+            ),
+            format!(
+                r#"{{"steps":[{0},{0}],"walk_len":2,"walk":0,"idx":3}}"#,
+                step
+            ),
+            format!(r#"{{"steps":[{0}],"walk_len":0,"walk":0,"idx":0}}"#, step),
+        ];
+        for json in &invalid {
+            let err = serde_json::from_str::<StepsPattern>(json).unwrap_err();
+            assert!(err.to_string().contains("does not match"), "{}", err);
+        }
+
+        let empty = r#"{"steps":[],"walk_len":0,"walk":0,"idx":0}"#;
+        let pattern: StepsPattern = serde_json::from_str(empty).unwrap();
+        assert_eq!(Grid::new(2, 2, ()).pattern((0, 0), pattern).count(), 0);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::grid::{Grid, Position};
+use crate::neighborhood::Neighborhood;
+use std::collections::VecDeque;
+
+impl<T> Grid<T> {
+    /// Returns the positions of the region around `pos`, which contains every cell
+    /// connected to `pos` over cells for which `predicate` returns true.
+    /// Cells are connected to their 4 orthogonal neighbors, see [Grid::flood_fill_with]
+    /// for other connectivity. The positions are ordered by their distance to `pos`.
+    /// Returns no positions if `predicate` returns false for `pos`.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from_str_map("
+    /// ..#
+    /// #.#
+    /// ", |c| c).unwrap();
+    /// let region = grid.flood_fill((0, 0), |cell, _| *cell == '.');
+    /// assert_eq!(region, vec![(0, 0).into(), (1, 0).into(), (1, 1).into()]);
+    /// ```
+    /// # Panics
+    /// * if `pos` is out of bounds.
+    pub fn flood_fill<P, F>(&self, pos: P, predicate: F) -> Vec<Position>
+    where
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        self.flood_fill_with(pos, &Neighborhood::von_neumann(), predicate)
+    }
+
+    /// Returns the positions of the region around `pos`, where cells are connected
+    /// to their `neighborhood`. See [Grid::flood_fill].
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood};
+    /// let grid = Grid::from_str_map("
+    /// .#
+    /// #.
+    /// ", |c| c).unwrap();
+    /// let region = grid.flood_fill_with((0, 0), &Neighborhood::moore(), |cell, _| *cell == '.');
+    /// assert_eq!(region.len(), 2);
+    /// ```
+    /// # Panics
+    /// * if `pos` is out of bounds.
+    pub fn flood_fill_with<P, F>(
+        &self,
+        pos: P,
+        neighborhood: &Neighborhood,
+        predicate: F,
+    ) -> Vec<Position>
+    where
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        let pos = pos.into();
+        self.check_bounds(pos).unwrap_or_else(|e| panic!("{}", e));
+        if !predicate(self.get_unchecked(pos), pos) {
+            return Vec::new();
+        }
+        let mut visited = vec![false; self.items.len()];
+        self.region(pos, neighborhood, &mut visited, |_, to| {
+            predicate(self.get_unchecked(to), to)
+        })
+    }
+
+    /// Sets every cell of the region around `pos` to `value` and returns the positions
+    /// of the region. See [Grid::flood_fill].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from_str_map("
+    /// .##
+    /// #..
+    /// ", |c| c).unwrap();
+    /// grid.flood_fill_mut((2, 1), |cell, _| *cell == '.', 'o');
+    /// assert_eq!(grid.display_with(|&c| c).to_string(), ".##\n#oo");
+    /// ```
+    /// # Panics
+    /// * if `pos` is out of bounds.
+    pub fn flood_fill_mut<P, F>(&mut self, pos: P, predicate: F, value: T) -> Vec<Position>
+    where
+        T: Clone,
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        self.flood_fill_mut_with(pos, &Neighborhood::von_neumann(), predicate, value)
+    }
+
+    /// Sets every cell of the region around `pos` to `value`, where cells are connected
+    /// to their `neighborhood`. See [Grid::flood_fill_mut].
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood};
+    /// let mut grid = Grid::from(vec![0, 1, 1, 0], 2, 2);
+    /// grid.flood_fill_mut_with((0, 0), &Neighborhood::moore(), |cell, _| *cell == 0, 2);
+    /// assert_eq!(grid, Grid::from(vec![2, 1, 1, 2], 2, 2));
+    /// ```
+    /// # Panics
+    /// * if `pos` is out of bounds.
+    pub fn flood_fill_mut_with<P, F>(
+        &mut self,
+        pos: P,
+        neighborhood: &Neighborhood,
+        predicate: F,
+        value: T,
+    ) -> Vec<Position>
+    where
+        T: Clone,
+        P: Into<Position>,
+        F: Fn(&T, Position) -> bool,
+    {
+        let region = self.flood_fill_with(pos, neighborhood, predicate);
+        for pos in &region {
+            let idx = self.translate(*pos);
+            self.items[idx] = value.clone();
+        }
+        region
+    }
+
+    /// Labels the connected components of the grid. Two orthogonal neighbors belong to
+    /// the same component if `eq` returns true for them, see [Grid::label_components_with]
+    /// for other connectivity.
+    /// Returns a grid with the component id of every cell and the size of every component,
+    /// ids start at 0 and are numbered in the order of the first cell of the component
+    /// from the top left to the bottom right.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from_str_map("
+    /// aab
+    /// bab
+    /// ", |c| c).unwrap();
+    /// let (labels, sizes) = grid.label_components(|a, b| a == b);
+    /// assert_eq!(labels, Grid::from(vec![0, 0, 1, 2, 0, 1], 3, 2));
+    /// assert_eq!(sizes, vec![3, 2, 1]);
+    /// ```
+    pub fn label_components<F>(&self, eq: F) -> (Grid<usize>, Vec<usize>)
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        self.label_components_with(&Neighborhood::von_neumann(), eq)
+    }
+
+    /// Labels the connected components of the grid, where cells are connected
+    /// to their `neighborhood`. See [Grid::label_components].
+    /// The neighborhood should be symmetric, otherwise the components depend on the
+    /// order in which they are found.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Neighborhood};
+    /// let grid = Grid::from(vec![1, 0, 0, 1], 2, 2);
+    /// let (_, sizes) = grid.label_components_with(&Neighborhood::moore(), |a, b| a == b);
+    /// assert_eq!(sizes, vec![2, 2]);
+    /// ```
+    pub fn label_components_with<F>(
+        &self,
+        neighborhood: &Neighborhood,
+        eq: F,
+    ) -> (Grid<usize>, Vec<usize>)
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut visited = vec![false; self.items.len()];
+        let mut labels = vec![0; self.items.len()];
+        let mut sizes = Vec::new();
+        for idx in 0..self.items.len() {
+            if visited[idx] {
+                continue;
+            }
+            let start = Position::new(idx % self.width, idx / self.width);
+            let component = self.region(start, neighborhood, &mut visited, |from, to| {
+                eq(self.get_unchecked(from), self.get_unchecked(to))
+            });
+            for pos in &component {
+                labels[self.translate(*pos)] = sizes.len();
+            }
+            sizes.push(component.len());
+        }
+        let labels = Grid {
+            items: labels,
+            width: self.width,
+            height: self.height,
+        };
+        (labels, sizes)
+    }
+
+    // Breadth first search from `start` over cells which are not `visited` yet,
+    // a neighbor is entered if `connected` returns true for the step to it.
+    // Returns the positions of the region including `start`.
+    fn region<F>(
+        &self,
+        start: Position,
+        neighborhood: &Neighborhood,
+        visited: &mut [bool],
+        connected: F,
+    ) -> Vec<Position>
+    where
+        F: Fn(Position, Position) -> bool,
+    {
+        let view = self.as_view();
+        visited[self.translate(start)] = true;
+        let mut region = vec![start];
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        while let Some(pos) = queue.pop_front() {
+            for next in view.neighbor_positions(pos, neighborhood) {
+                let idx = self.translate(next);
+                if visited[idx] || !connected(pos, next) {
+                    continue;
+                }
+                visited[idx] = true;
+                region.push(next);
+                queue.push_back(next);
+            }
+        }
+        region
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cave() -> Grid<char> {
+        Grid::from_str_map(
+            "
+..#..
+.##..
+#..#.
+#..##",
+            |c| c,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn flood_fill_connectivity() {
+        let grid = cave();
+        let open = |cell: &char, _| *cell == '.';
+        assert_eq!(grid.flood_fill((0, 0), open).len(), 3);
+        assert_eq!(grid.flood_fill((1, 3), open).len(), 4);
+        assert_eq!(grid.flood_fill((2, 0), open), vec![]);
+        // diagonally the top left cave connects to the bottom cave
+        assert_eq!(
+            grid.flood_fill_with((0, 0), &Neighborhood::moore(), open)
+                .len(),
+            12
+        );
+    }
+
+    #[test]
+    fn flood_fill_mut_paints_region() {
+        let mut grid = cave();
+        let region = grid.flood_fill_mut((4, 0), |cell, _| *cell == '.', '~');
+        assert_eq!(region.len(), 5);
+        assert_eq!(
+            grid.display_with(|&c| c).to_string(),
+            "..#~~\n.##~~\n#..#~\n#..##"
+        );
+    }
+
+    #[test]
+    fn label_caves() {
+        let (labels, sizes) = cave().label_components(|a, b| a == b);
+        let expected = Grid::from_str_map("00122\n01122\n34452\n34455", |c| {
+            c.to_digit(10).unwrap() as usize
+        })
+        .unwrap();
+        assert_eq!(labels, expected);
+        assert_eq!(sizes, vec![3, 3, 5, 2, 4, 3]);
+
+        let (_, sizes) = cave().label_components_with(&Neighborhood::moore(), |a, b| a == b);
+        assert_eq!(sizes, vec![12, 8]);
+    }
+}
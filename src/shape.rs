@@ -0,0 +1,756 @@
+use crate::grid::{Grid, Position, Rect};
+use crate::iter::{PositionList, ShapeIter, ShapeIterMut};
+
+// A line from `a` to `b` drawn with Bresenham's algorithm
+struct Line {
+    a: Position,
+    b: Position,
+    dx: usize,
+    dy: usize,
+}
+
+impl Line {
+    fn new(a: Position, b: Position) -> Self {
+        Self {
+            a,
+            b,
+            dx: a.x.abs_diff(b.x),
+            dy: a.y.abs_diff(b.y),
+        }
+    }
+
+    // The number of steps from `a` to `b`
+    fn steps(&self) -> usize {
+        self.dx.max(self.dy)
+    }
+
+    // The position after `t` steps. The major axis moves every step, the offset on the
+    // minor axis is t * minor / major rounded to the nearest integer, halves rounded up.
+    fn at(&self, t: usize) -> Position {
+        let (major, minor) = (self.steps() as u128, self.dx.min(self.dy) as u128);
+        let offset = match major {
+            0 => 0,
+            _ => {
+                let q = t as u128 * minor;
+                (q / major + u128::from(2 * (q % major) >= major)) as usize
+            }
+        };
+        let (x, y) = match self.dx >= self.dy {
+            true => (t, offset),
+            false => (offset, t),
+        };
+        Position::new(
+            towards(self.a.x, self.b.x, x),
+            towards(self.a.y, self.b.y, y),
+        )
+    }
+}
+
+// Moves `offset` from `from` in the direction of `to`
+fn towards(from: usize, to: usize, offset: usize) -> usize {
+    match to >= from {
+        true => from + offset,
+        false => from - offset,
+    }
+}
+
+// The positions of the line from `a` to `b`, both ends included, which are inside a grid of `size`.
+fn line_positions(a: Position, b: Position, (width, height): (usize, usize)) -> Vec<Position> {
+    let line = Line::new(a, b);
+    // The coordinates change monotonically, so the cells inside the grid are in one piece.
+    // It starts at the first step which is inside the grid on the axes moving towards zero.
+    let entered = |t| {
+        let pos = line.at(t);
+        (b.x >= a.x || pos.x < width) && (b.y >= a.y || pos.y < height)
+    };
+    let (mut lo, mut hi) = (0, line.steps());
+    if !entered(hi) {
+        return Vec::new();
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match entered(mid) {
+            true => hi = mid,
+            false => lo = mid + 1,
+        }
+    }
+    (lo..=line.steps())
+        .map(|t| line.at(t))
+        .take_while(|pos| pos.x < width && pos.y < height)
+        .collect()
+}
+
+// The positions on the border of the rectangle from (left, top) to (right, bottom),
+// which are inside a grid of `size`, clockwise from the top left corner.
+fn outline_positions(
+    (left, top): (i128, i128),
+    (right, bottom): (i128, i128),
+    (width, height): (usize, usize),
+) -> Vec<Position> {
+    let (width, height) = (width as i128, height as i128);
+    let xs = |from: i128, to: i128| from.max(0)..=to.min(width - 1);
+    let ys = |from: i128, to: i128| from.max(0)..=to.min(height - 1);
+    let pos = |x: i128, y: i128| Position::new(x as usize, y as usize);
+    let mut positions = Vec::new();
+    if (0..height).contains(&top) {
+        positions.extend(xs(left, right).map(|x| pos(x, top)));
+    }
+    if (0..width).contains(&right) {
+        positions.extend(ys(top + 1, bottom).map(|y| pos(right, y)));
+    }
+    if left < right && top < bottom {
+        if (0..height).contains(&bottom) {
+            positions.extend(xs(left, right - 1).rev().map(|x| pos(x, bottom)));
+        }
+        if (0..width).contains(&left) {
+            positions.extend(ys(top + 1, bottom - 1).rev().map(|y| pos(left, y)));
+        }
+    }
+    positions
+}
+
+// The positions on the border of `rect`, which are inside a grid of `size`,
+// clockwise from the top left corner
+fn rect_outline_positions(rect: Rect, size: (usize, usize)) -> Vec<Position> {
+    if rect.width == 0 || rect.height == 0 {
+        return Vec::new();
+    }
+    let (left, top) = (rect.x as i128, rect.y as i128);
+    let (right, bottom) = (left + rect.width as i128 - 1, top + rect.height as i128 - 1);
+    outline_positions((left, top), (right, bottom), size)
+}
+
+// The positions inside `rect` and a grid of `size`, from the top left to the bottom right
+fn rect_filled_positions(rect: Rect, (width, height): (usize, usize)) -> Vec<Position> {
+    let xs = rect.x..rect.x.saturating_add(rect.width).min(width);
+    (rect.y..rect.y.saturating_add(rect.height).min(height))
+        .flat_map(|y| xs.clone().map(move |x| (x, y).into()))
+        .collect()
+}
+
+// The positions within `radius` of `center`, which are inside a grid of `size`,
+// from the top left to the bottom right. Only the border of the circle if `outline` is true.
+fn circle_positions(
+    center: Position,
+    radius: usize,
+    outline: bool,
+    (width, height): (usize, usize),
+) -> Vec<Position> {
+    let r = radius as u128;
+    let max_distance = r.saturating_mul(r).saturating_add(r);
+    let inside = |dx: i128, dy: i128| {
+        let (dx, dy) = (dx.unsigned_abs(), dy.unsigned_abs());
+        dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy)) <= max_distance
+    };
+    let on_border = |dx, dy| {
+        !inside(dx - 1, dy) || !inside(dx + 1, dy) || !inside(dx, dy - 1) || !inside(dx, dy + 1)
+    };
+    // Only the part of the bounding box of the circle inside the grid is checked
+    let xs = center.x.saturating_sub(radius)..=center.x.saturating_add(radius).min(width - 1);
+    let ys = center.y.saturating_sub(radius)..=center.y.saturating_add(radius).min(height - 1);
+    let mut positions = Vec::new();
+    for y in ys {
+        for x in xs.clone() {
+            let (dx, dy) = (x as i128 - center.x as i128, y as i128 - center.y as i128);
+            if inside(dx, dy) && (!outline || on_border(dx, dy)) {
+                positions.push((x, y).into());
+            }
+        }
+    }
+    positions
+}
+
+// The positions at chebyshev distance `radius` of `center`, which are inside a grid of `size`,
+// clockwise from the top left corner.
+fn ring_positions(center: Position, radius: usize, size: (usize, usize)) -> Vec<Position> {
+    let (x, y, r) = (center.x as i128, center.y as i128, radius as i128);
+    outline_positions((x - r, y - r), (x + r, y + r), size)
+}
+
+impl<T> Grid<T> {
+    // Iterator over the cells at `positions`, which have to be inside the grid
+    fn shape(&self, positions: Vec<Position>) -> ShapeIter<'_, T> {
+        ShapeIter {
+            positions: PositionList::new(positions),
+            grid: self.as_view(),
+        }
+    }
+
+    // Iterator over the cells at the distinct `positions`, which have to be inside the grid
+    fn shape_mut(&mut self, positions: Vec<Position>) -> ShapeIterMut<'_, T> {
+        let cells = self.get_many_mut(&positions);
+        ShapeIterMut {
+            positions: PositionList::new(positions),
+            cells: cells.into_iter(),
+        }
+    }
+
+    /// Creates an iterator which yields references of every cell on the line from `a` to `b`,
+    /// both ends included. Cells outside the grid are skipped.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, PositionsEnumerator};
+    /// let grid = Grid::new(5, 3, 0);
+    /// let line: Vec<_> = grid.line((0, 0), (4, 2)).grid_positions().map(|(pos, _)| pos).collect();
+    /// assert_eq!(line, vec![(0, 0).into(), (1, 1).into(), (2, 1).into(), (3, 2).into(), (4, 2).into()]);
+    /// ```
+    pub fn line<P: Into<Position>>(&self, a: P, b: P) -> ShapeIter<'_, T> {
+        self.shape(line_positions(a.into(), b.into(), self.size()))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell on the line from `a` to `b`.
+    /// See [Grid::line].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// grid.line_mut((2, 0), (0, 2)).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![0, 0, 1, 0, 1, 0, 1, 0, 0], 3, 3));
+    /// ```
+    pub fn line_mut<P: Into<Position>>(&mut self, a: P, b: P) -> ShapeIterMut<'_, T> {
+        let size = self.size();
+        self.shape_mut(line_positions(a.into(), b.into(), size))
+    }
+
+    /// Creates an iterator which yields references of every cell on the border of `rect`,
+    /// clockwise from the top left corner. Cells outside the grid are skipped.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// // 0, 1, 2
+    /// // 3, 4, 5
+    /// // 6, 7, 8
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let border: Vec<_> = grid.rect_outline(Rect::new((0, 0), 3, 3)).collect();
+    /// assert_eq!(border, vec![&0, &1, &2, &5, &8, &7, &6, &3]);
+    /// ```
+    pub fn rect_outline(&self, rect: Rect) -> ShapeIter<'_, T> {
+        self.shape(rect_outline_positions(rect, self.size()))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell on the border of `rect`.
+    /// See [Grid::rect_outline].
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// grid.rect_outline_mut(Rect::new((0, 0), 3, 3)).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![1, 1, 1, 1, 0, 1, 1, 1, 1], 3, 3));
+    /// ```
+    pub fn rect_outline_mut(&mut self, rect: Rect) -> ShapeIterMut<'_, T> {
+        let size = self.size();
+        self.shape_mut(rect_outline_positions(rect, size))
+    }
+
+    /// Creates an iterator which yields references of every cell inside `rect`,
+    /// from the top left to the bottom right. Cells outside the grid are skipped.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// // 0, 1, 2
+    /// // 3, 4, 5
+    /// let grid = Grid::from((0..6).collect(), 3, 2);
+    /// let cells: Vec<_> = grid.rect_filled(Rect::new((1, 0), 5, 5)).collect();
+    /// assert_eq!(cells, vec![&1, &2, &4, &5]);
+    /// ```
+    pub fn rect_filled(&self, rect: Rect) -> ShapeIter<'_, T> {
+        self.shape(rect_filled_positions(rect, self.size()))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell inside `rect`.
+    /// See [Grid::rect_filled].
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::new(3, 2, 0);
+    /// grid.rect_filled_mut(Rect::new((1, 0), 2, 2)).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![0, 1, 1, 0, 1, 1], 3, 2));
+    /// ```
+    pub fn rect_filled_mut(&mut self, rect: Rect) -> ShapeIterMut<'_, T> {
+        let size = self.size();
+        self.shape_mut(rect_filled_positions(rect, size))
+    }
+
+    /// Creates an iterator which yields references of every cell on the border of the circle
+    /// around `center`, from the top left to the bottom right. Cells outside the grid are skipped.
+    /// The border are the cells of [Grid::filled_circle], which have an orthogonal neighbor
+    /// outside the circle.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::new(5, 5, 0);
+    /// assert_eq!(grid.circle((2, 2), 0).count(), 1);
+    /// assert_eq!(grid.circle((2, 2), 1).count(), 8);
+    /// assert_eq!(grid.circle((2, 2), 2).count(), 12);
+    /// ```
+    pub fn circle<P: Into<Position>>(&self, center: P, radius: usize) -> ShapeIter<'_, T> {
+        self.shape(circle_positions(center.into(), radius, true, self.size()))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell on the border of the circle
+    /// around `center`. See [Grid::circle].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// grid.circle_mut((1, 1), 1).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![1, 1, 1, 1, 0, 1, 1, 1, 1], 3, 3));
+    /// ```
+    pub fn circle_mut<P: Into<Position>>(
+        &mut self,
+        center: P,
+        radius: usize,
+    ) -> ShapeIterMut<'_, T> {
+        let size = self.size();
+        self.shape_mut(circle_positions(center.into(), radius, true, size))
+    }
+
+    /// Creates an iterator which yields references of every cell within `radius` of `center`,
+    /// from the top left to the bottom right. Cells outside the grid are skipped.
+    /// A cell is within `radius` if dx² + dy² <= radius² + radius, like in [Grid::fov].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::new(5, 5, 0);
+    /// assert_eq!(grid.filled_circle((2, 2), 2).count(), 21);
+    /// assert_eq!(grid.filled_circle((0, 0), 2).count(), 8);
+    /// ```
+    pub fn filled_circle<P: Into<Position>>(&self, center: P, radius: usize) -> ShapeIter<'_, T> {
+        self.shape(circle_positions(center.into(), radius, false, self.size()))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell within `radius` of `center`.
+    /// See [Grid::filled_circle].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// grid.filled_circle_mut((0, 0), 1).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![1, 1, 0, 1, 1, 0, 0, 0, 0], 3, 3));
+    /// ```
+    pub fn filled_circle_mut<P: Into<Position>>(
+        &mut self,
+        center: P,
+        radius: usize,
+    ) -> ShapeIterMut<'_, T> {
+        let size = self.size();
+        self.shape_mut(circle_positions(center.into(), radius, false, size))
+    }
+
+    /// Creates an iterator which yields references of every cell on the diagonal `k`,
+    /// from the top left to the bottom right. The diagonal `k` contains the positions
+    /// where x - y equals `k`, so 0 is the main diagonal and positive diagonals are above it.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// // 0, 1, 2
+    /// // 3, 4, 5
+    /// // 6, 7, 8
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// assert_eq!(grid.diagonal(0).collect::<Vec<_>>(), vec![&0, &4, &8]);
+    /// assert_eq!(grid.diagonal(1).collect::<Vec<_>>(), vec![&1, &5]);
+    /// assert_eq!(grid.diagonal(-2).collect::<Vec<_>>(), vec![&6]);
+    /// ```
+    /// # Panics
+    /// * if the diagonal does not contain any cell of the grid.
+    pub fn diagonal(&self, k: isize) -> ShapeIter<'_, T> {
+        self.shape(self.diagonal_positions(k))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell on the diagonal `k`.
+    /// See [Grid::diagonal].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::new(3, 2, 0);
+    /// grid.diagonal_mut(1).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![0, 1, 0, 0, 0, 1], 3, 2));
+    /// ```
+    /// # Panics
+    /// * if the diagonal does not contain any cell of the grid.
+    pub fn diagonal_mut(&mut self, k: isize) -> ShapeIterMut<'_, T> {
+        let positions = self.diagonal_positions(k);
+        self.shape_mut(positions)
+    }
+
+    /// Creates an iterator which yields references of every cell on the anti-diagonal `k`,
+    /// from the top right to the bottom left. The anti-diagonal `k` contains the positions
+    /// where x + y equals `k`.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// // 0, 1, 2
+    /// // 3, 4, 5
+    /// // 6, 7, 8
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// assert_eq!(grid.anti_diagonal(2).collect::<Vec<_>>(), vec![&2, &4, &6]);
+    /// assert_eq!(grid.anti_diagonal(3).collect::<Vec<_>>(), vec![&5, &7]);
+    /// ```
+    /// # Panics
+    /// * if the anti-diagonal does not contain any cell of the grid.
+    pub fn anti_diagonal(&self, k: usize) -> ShapeIter<'_, T> {
+        self.shape(self.anti_diagonal_positions(k))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell on the anti-diagonal `k`.
+    /// See [Grid::anti_diagonal].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// grid.anti_diagonal_mut(1).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![0, 1, 1, 0], 2, 2));
+    /// ```
+    /// # Panics
+    /// * if the anti-diagonal does not contain any cell of the grid.
+    pub fn anti_diagonal_mut(&mut self, k: usize) -> ShapeIterMut<'_, T> {
+        let positions = self.anti_diagonal_positions(k);
+        self.shape_mut(positions)
+    }
+
+    /// Creates an iterator which yields references of every cell at chebyshev distance `radius`
+    /// of `center`, the border of the square around `center`, clockwise from the top left corner.
+    /// Cells outside the grid are skipped.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// // 0, 1, 2
+    /// // 3, 4, 5
+    /// // 6, 7, 8
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// assert_eq!(grid.ring((1, 1), 1).collect::<Vec<_>>(), vec![&0, &1, &2, &5, &8, &7, &6, &3]);
+    /// assert_eq!(grid.ring((0, 0), 1).collect::<Vec<_>>(), vec![&1, &4, &3]);
+    /// ```
+    pub fn ring<P: Into<Position>>(&self, center: P, radius: usize) -> ShapeIter<'_, T> {
+        self.shape(ring_positions(center.into(), radius, self.size()))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell at chebyshev distance `radius`
+    /// of `center`. See [Grid::ring].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// grid.ring_mut((2, 2), 2).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![1, 1, 1, 1, 0, 0, 1, 0, 0], 3, 3));
+    /// ```
+    pub fn ring_mut<P: Into<Position>>(&mut self, center: P, radius: usize) -> ShapeIterMut<'_, T> {
+        let size = self.size();
+        self.shape_mut(ring_positions(center.into(), radius, size))
+    }
+
+    /// Creates an iterator which yields references of every cell of the grid ordered by their
+    /// chebyshev distance to `from`. It walks the [Grid::ring]s around `from` outwards,
+    /// which makes it useful to find the nearest cell with some property.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, PositionsEnumerator};
+    /// let grid = Grid::from_str_map("
+    /// xx..
+    /// xxx.
+    /// ", |c| c).unwrap();
+    /// let nearest_free = grid
+    ///     .spiral((0, 1))
+    ///     .grid_positions()
+    ///     .find(|(_, cell)| **cell == '.')
+    ///     .map(|(pos, _)| pos);
+    /// assert_eq!(nearest_free, Some((2, 0).into()));
+    /// ```
+    /// # Panics
+    /// * if `from` is out of bounds.
+    pub fn spiral<P: Into<Position>>(&self, from: P) -> ShapeIter<'_, T> {
+        self.shape(self.spiral_positions(from.into()))
+    }
+
+    /// Creates an iterator which yields mutable references of every cell of the grid ordered by their
+    /// chebyshev distance to `from`. See [Grid::spiral].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::new(3, 1, 0);
+    /// grid.spiral_mut((1, 0)).enumerate().for_each(|(i, cell)| *cell = i);
+    /// assert_eq!(grid, Grid::from(vec![2, 0, 1], 3, 1));
+    /// ```
+    /// # Panics
+    /// * if `from` is out of bounds.
+    pub fn spiral_mut<P: Into<Position>>(&mut self, from: P) -> ShapeIterMut<'_, T> {
+        let positions = self.spiral_positions(from.into());
+        self.shape_mut(positions)
+    }
+
+    fn diagonal_positions(&self, k: isize) -> Vec<Position> {
+        assert!(
+            k > -(self.height as isize) && k < self.width as isize,
+            "diagonal {} is out of bounds",
+            k
+        );
+        let (x, y) = match k >= 0 {
+            true => (k as usize, 0),
+            false => (0, k.unsigned_abs()),
+        };
+        (0..(self.width - x).min(self.height - y))
+            .map(|i| Position::new(x + i, y + i))
+            .collect()
+    }
+
+    fn anti_diagonal_positions(&self, k: usize) -> Vec<Position> {
+        assert!(
+            k < self.width + self.height - 1,
+            "anti-diagonal {} is out of bounds",
+            k
+        );
+        let y = k.saturating_sub(self.width - 1);
+        (y..=k.min(self.height - 1))
+            .map(|y| Position::new(k - y, y))
+            .collect()
+    }
+
+    fn spiral_positions(&self, from: Position) -> Vec<Position> {
+        self.check_bounds(from).unwrap_or_else(|e| panic!("{}", e));
+        let max_radius = from
+            .x
+            .max(from.y)
+            .max(self.width - 1 - from.x)
+            .max(self.height - 1 - from.y);
+        (0..=max_radius)
+            .flat_map(|radius| ring_positions(from, radius, self.size()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PositionsEnumerator;
+
+    fn positions(v: Vec<(usize, usize)>) -> Vec<Position> {
+        v.into_iter().map(Position::from).collect()
+    }
+
+    // Bresenham's algorithm walking every step from `a` to `b`
+    fn bresenham(a: (isize, isize), b: (isize, isize)) -> Vec<(isize, isize)> {
+        let (mut x, mut y) = a;
+        let dx = (b.0 - x).abs();
+        let dy = -(b.1 - y).abs();
+        let (sx, sy) = ((b.0 - x).signum(), (b.1 - y).signum());
+        let mut err = dx + dy;
+        let mut positions = vec![(x, y)];
+        while (x, y) != b {
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+            positions.push((x, y));
+        }
+        positions
+    }
+
+    #[test]
+    fn line_matches_bresenham() {
+        let long = [(1000, 3), (4, 999), (997, 998)];
+        let ends: Vec<(isize, isize)> = (0..9)
+            .flat_map(|x| (0..9).map(move |y| (x, y)))
+            .chain(long)
+            .collect();
+        for &a in &ends {
+            for &b in &ends {
+                let expected: Vec<Position> = bresenham(a, b)
+                    .into_iter()
+                    .filter(|(x, y)| *x < 6 && *y < 5)
+                    .map(|(x, y)| (x as usize, y as usize).into())
+                    .collect();
+                let a = (a.0 as usize, a.1 as usize).into();
+                let b = (b.0 as usize, b.1 as usize).into();
+                assert_eq!(line_positions(a, b, (6, 5)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn line_is_clipped() {
+        let forward = line_positions((0, 1).into(), (5, 3).into(), (6, 6));
+        assert_eq!(forward.len(), 6);
+        assert_eq!(forward.first(), Some(&(0, 1).into()));
+        assert_eq!(forward.last(), Some(&(5, 3).into()));
+
+        let grid = Grid::new(4, 4, 0);
+        let clipped: Vec<_> = grid
+            .line((0, 0), (10, 0))
+            .grid_positions()
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(clipped, positions(vec![(0, 0), (1, 0), (2, 0), (3, 0)]));
+        assert_eq!(grid.line((2, 2), (2, 2)).count(), 1);
+        assert_eq!(grid.line((5, 5), (9, 9)).count(), 0);
+    }
+
+    #[test]
+    fn oversized_shapes_are_clipped() {
+        let mut grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.line((0, 0), (1 << 40, 0)).count(), 3);
+        assert_eq!(grid.line((1 << 40, 0), (0, 0)).count(), 3);
+        assert_eq!(grid.line((usize::MAX, usize::MAX), (0, 0)).count(), 3);
+        assert_eq!(grid.line((0, usize::MAX), (usize::MAX, 0)).count(), 0);
+        assert_eq!(grid.line_mut((2, 1 << 40), (2, 0)).count(), 3);
+
+        let rect = Rect::new((1, 1), usize::MAX, 2);
+        assert_eq!(grid.rect_filled(rect).count(), 4);
+        assert_eq!(grid.rect_filled_mut(rect).count(), 4);
+        assert_eq!(grid.rect_outline(rect).count(), 4);
+        let huge = Rect::new((1, 0), usize::MAX, usize::MAX);
+        assert_eq!(grid.rect_outline_mut(huge).count(), 2 + 2);
+
+        assert_eq!(grid.filled_circle((1, 1), 1 << 33).count(), 9);
+        assert_eq!(grid.circle((1, 1), 1 << 33).count(), 0);
+        assert_eq!(grid.filled_circle_mut((1 << 40, 0), usize::MAX).count(), 9);
+    }
+
+    #[test]
+    fn rect_outline_mut_clipped() {
+        let mut grid = Grid {
+            width: 4,
+            height: 3,
+            items: vec![0; 12],
+        };
+        let mut border = grid
+            .rect_outline_mut(Rect::new((2, 1), 4, 4))
+            .grid_positions();
+        assert_eq!(border.next(), Some(((2, 1).into(), &mut 0)));
+        assert_eq!(border.next(), Some(((3, 1).into(), &mut 0)));
+        assert_eq!(border.next(), Some(((2, 2).into(), &mut 0)));
+        assert_eq!(border.next(), None);
+    }
+
+    #[test]
+    fn circle_is_border_of_filled_circle() {
+        let grid = Grid {
+            width: 9,
+            height: 9,
+            items: (0..81).collect(),
+        };
+        let filled: Vec<_> = grid.filled_circle((4, 4), 3).collect();
+        let circle: Vec<_> = grid.circle((4, 4), 3).collect();
+        assert_eq!(filled.len(), 37);
+        assert_eq!(circle.len(), 16);
+        assert!(circle.iter().all(|cell| filled.contains(cell)));
+        // the center is never part of the border for a radius greater than 0
+        assert!(!circle.contains(&&40));
+    }
+
+    #[test]
+    fn diagonals_of_rectangular_grid() {
+        // 0, 1, 2, 3
+        // 4, 5, 6, 7
+        let grid = Grid {
+            width: 4,
+            height: 2,
+            items: (0..8).collect(),
+        };
+        assert_eq!(grid.diagonal(2).collect::<Vec<_>>(), vec![&2, &7]);
+        assert_eq!(grid.diagonal(3).collect::<Vec<_>>(), vec![&3]);
+        assert_eq!(grid.diagonal(-1).collect::<Vec<_>>(), vec![&4]);
+        assert_eq!(grid.anti_diagonal(0).collect::<Vec<_>>(), vec![&0]);
+        assert_eq!(grid.anti_diagonal(3).collect::<Vec<_>>(), vec![&3, &6]);
+        assert_eq!(grid.anti_diagonal(4).collect::<Vec<_>>(), vec![&7]);
+
+        let mut diagonal = grid.anti_diagonal(1).grid_positions();
+        assert_eq!(diagonal.next(), Some(((1, 0).into(), &1)));
+        assert_eq!(diagonal.next(), Some(((0, 1).into(), &4)));
+        assert_eq!(diagonal.next(), None);
+    }
+
+    #[test]
+    fn shapes_from_the_back() {
+        // 0, 1, 2
+        // 3, 4, 5
+        // 6, 7, 8
+        let mut grid = Grid {
+            width: 3,
+            height: 3,
+            items: (0..9).collect(),
+        };
+        let diagonal = grid.diagonal(0);
+        assert_eq!(diagonal.len(), 3);
+        assert_eq!(diagonal.rev().collect::<Vec<_>>(), vec![&8, &4, &0]);
+
+        let mut line = grid.line((0, 1), (2, 1)).grid_positions();
+        assert_eq!(line.len(), 3);
+        assert_eq!(line.next_back(), Some(((2, 1).into(), &5)));
+        assert_eq!(line.next(), Some(((0, 1).into(), &3)));
+        assert_eq!(line.next_back(), Some(((1, 1).into(), &4)));
+        assert_eq!(line.next(), None);
+
+        let mut anti_diagonal = grid.anti_diagonal_mut(2).grid_positions();
+        assert_eq!(anti_diagonal.len(), 3);
+        assert_eq!(anti_diagonal.next_back(), Some(((0, 2).into(), &mut 6)));
+        assert_eq!(anti_diagonal.next(), Some(((2, 0).into(), &mut 2)));
+        assert_eq!(anti_diagonal.len(), 1);
+        assert_eq!(anti_diagonal.next_back(), Some(((1, 1).into(), &mut 4)));
+        assert_eq!(anti_diagonal.next_back(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "diagonal -2 is out of bounds")]
+    fn diagonal_out_of_bounds() {
+        Grid::new(4, 2, 0).diagonal(-2);
+    }
+
+    #[test]
+    fn spiral_visits_every_cell_once() {
+        let mut grid = Grid::new(5, 4, 0);
+        grid.spiral_mut((1, 2)).for_each(|cell| *cell += 1);
+        assert!(grid.items.iter().all(|cell| *cell == 1));
+
+        let positions: Vec<_> = grid
+            .spiral((1, 2))
+            .grid_positions()
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(positions.len(), 20);
+        assert_eq!(positions[0], (1, 2).into());
+        assert_eq!(positions[1], (0, 1).into());
+        assert_eq!(positions.last(), Some(&(4, 3).into()));
+    }
+
+    #[test]
+    fn ring_positions_clockwise() {
+        assert_eq!(
+            ring_positions((1, 1).into(), 1, (3, 3)),
+            positions(vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1)
+            ])
+        );
+        assert_eq!(ring_positions((0, 0).into(), 2, (3, 3)).len(), 5);
+        assert_eq!(
+            ring_positions((1, 1).into(), 0, (3, 3)),
+            positions(vec![(1, 1)])
+        );
+    }
+
+    #[test]
+    fn huge_rings_are_clipped() {
+        let mut grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.ring((1, 1), usize::MAX).count(), 0);
+        assert_eq!(grid.ring((1, 1), 1 << 40).count(), 0);
+        assert_eq!(grid.ring_mut((1 << 40, 1), (1 << 40) - 2).count(), 3);
+        assert_eq!(
+            grid.ring((usize::MAX, usize::MAX), usize::MAX - 2)
+                .grid_positions()
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>(),
+            positions(vec![(2, 2)])
+        );
+    }
+}
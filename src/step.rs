@@ -1,48 +1,27 @@
 use super::Position;
-// Utility Enum for storing Negative(N) and Positive(P) as usize
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub(crate) enum N {
-    //Negative Number
-    N(usize),
-    //Positive Number
-    P(usize),
-}
-
-impl N {
-    fn get_number(&self) -> usize {
-        match self {
-            N::N(n) => *n,
-            N::P(n) => *n,
-        }
-    }
-
-    fn from_isize(n: isize) -> Self {
-        if n < 0 {
-            return N::N((-n) as usize);
-        }
-        N::P(n as usize)
-    }
-
-    fn from_i32(n: i32) -> Self {
-        if n < 0 {
-            return N::N((-n) as usize);
-        }
-        N::P(n as usize)
-    }
-
-    pub(crate) fn checked_add_sub(&self, n: usize) -> Option<usize> {
-        Some(match self {
-            N::N(pn) => n.checked_sub(*pn)?,
-            N::P(pn) => n.checked_add(*pn)?,
-        })
-    }
-}
+use std::convert::TryFrom;
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 /// A Step or direction to the next position in the grid, always relative to a position.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+///
+/// A step is a signed 2D vector which can be added, subtracted, scaled and negated.
+/// The operators overflow like integers, the `checked_` functions return `None` instead.
+/// Adding a step to a [Position] is checked and returns `None` if the result
+/// would be negative or overflow.
+/// # Example
+/// ```
+/// # use gridit::{Position, Step};
+/// let knight = Step::new(1, -2);
+/// assert_eq!(knight * 2 - Step::new(0, 1), Step::new(2, -5));
+/// assert_eq!(Position::new(2, 3) + knight, Some(Position::new(3, 1)));
+/// assert_eq!(Position::new(2, 1) + knight, None);
+/// ```
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Step {
-    x: N,
-    y: N,
+    pub x: isize,
+    pub y: isize,
 }
 
 impl Step {
@@ -50,18 +29,14 @@ impl Step {
     /// # Example
     /// ```
     /// # use gridit::Step;
-    /// let step = Step::new(1, 2);
-    /// assert_eq!(step, Step::from((1, 2)));
+    /// let step = Step::new(1, -2);
+    /// assert_eq!(step, Step::from((1, -2)));
     /// ```
-    pub fn new(x: usize, y: usize) -> Self {
-        Step {
-            x: N::P(x),
-            y: N::P(y),
-        }
+    pub fn new(x: isize, y: isize) -> Self {
+        Step { x, y }
     }
 
     /// Negate the x value of the step.
-    /// This is usefull when a step is needed with a `x` value larger then `i32`.
     /// ```
     /// # use gridit::Step;
     /// let step = Step::new(2, 2)
@@ -69,12 +44,11 @@ impl Step {
     /// assert_eq!(step, Step::from((-2, 2)));
     /// ```
     pub fn negate_x(mut self) -> Self {
-        self.x = N::N(self.x.get_number());
+        self.x = -self.x;
         self
     }
 
     /// Negate the y value of the step.
-    /// This is usefull when a step is needed with a `y` value larger then `i32`.
     /// ```
     /// # use gridit::Step;
     /// let step = Step::new(2, 2)
@@ -82,39 +56,452 @@ impl Step {
     /// assert_eq!(step, (2, -2).into());
     /// ```
     pub fn negate_y(mut self) -> Self {
-        self.y = N::N(self.y.get_number());
+        self.y = -self.y;
         self
     }
 
-    pub(crate) fn take_step_from_position(&self, pos: Position) -> Option<Position> {
-        let x = self.x.checked_add_sub(pos.x)?;
-        let y = self.y.checked_add_sub(pos.y)?;
+    /// Converts the step into a position, returns `None` if x or y is negative.
+    /// # Example
+    /// ```
+    /// # use gridit::{Position, Step};
+    /// assert_eq!(Step::new(1, 2).to_position(), Some(Position::new(1, 2)));
+    /// assert_eq!(Step::new(1, -2).to_position(), None);
+    /// ```
+    pub fn to_position(self) -> Option<Position> {
+        Position::try_from(self).ok()
+    }
+
+    /// Adds the steps, returns `None` if x or y overflows.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// assert_eq!(Step::new(1, 2).checked_add(Step::new(1, -1)), Some(Step::new(2, 1)));
+    /// assert_eq!(Step::new(isize::MAX, 0).checked_add(Step::new(1, 0)), None);
+    /// ```
+    pub fn checked_add(self, rhs: Step) -> Option<Step> {
+        Some(Step::new(
+            self.x.checked_add(rhs.x)?,
+            self.y.checked_add(rhs.y)?,
+        ))
+    }
+
+    /// Subtracts `rhs` from the step, returns `None` if x or y overflows.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// assert_eq!(Step::new(1, 2).checked_sub(Step::new(1, -1)), Some(Step::new(0, 3)));
+    /// assert_eq!(Step::new(isize::MIN, 0).checked_sub(Step::new(1, 0)), None);
+    /// ```
+    pub fn checked_sub(self, rhs: Step) -> Option<Step> {
+        Some(Step::new(
+            self.x.checked_sub(rhs.x)?,
+            self.y.checked_sub(rhs.y)?,
+        ))
+    }
+
+    /// Scales the step by `rhs`, returns `None` if x or y overflows.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// assert_eq!(Step::new(1, -2).checked_mul(3), Some(Step::new(3, -6)));
+    /// assert_eq!(Step::new(isize::MAX, 0).checked_mul(2), None);
+    /// ```
+    pub fn checked_mul(self, rhs: i32) -> Option<Step> {
+        let rhs = rhs as isize;
+        Some(Step::new(
+            self.x.checked_mul(rhs)?,
+            self.y.checked_mul(rhs)?,
+        ))
+    }
+
+    /// Negates the step, returns `None` if x or y is `isize::MIN`.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// assert_eq!(Step::new(1, -2).checked_neg(), Some(Step::new(-1, 2)));
+    /// assert_eq!(Step::new(isize::MIN, 0).checked_neg(), None);
+    /// ```
+    pub fn checked_neg(self) -> Option<Step> {
+        Some(Step::new(self.x.checked_neg()?, self.y.checked_neg()?))
+    }
+
+    /// Rotates the step by 90 degrees clockwise, where y points down like in the grid.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// // east turns to south
+    /// assert_eq!(Step::new(1, 0).rotate90(), Step::new(0, 1));
+    /// assert_eq!(Step::new(1, -2).rotate90(), Step::new(2, 1));
+    /// ```
+    pub fn rotate90(self) -> Self {
+        Step::new(-self.y, self.x)
+    }
+
+    /// Reflects the step on the vertical axis, which negates x and turns east to west.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// assert_eq!(Step::new(1, -2).reflect_x(), Step::new(-1, -2));
+    /// ```
+    pub fn reflect_x(self) -> Self {
+        self.negate_x()
+    }
+
+    /// Reflects the step on the horizontal axis, which negates y and turns north to south.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// assert_eq!(Step::new(1, -2).reflect_y(), Step::new(1, 2));
+    /// ```
+    pub fn reflect_y(self) -> Self {
+        self.negate_y()
+    }
+
+    /// Returns the unique images of the step under all 8 rotations and reflections
+    /// of the square. The first image is the step itself.
+    /// # Example
+    /// ```
+    /// # use gridit::Step;
+    /// assert_eq!(Step::new(1, 2).symmetries().len(), 8);
+    /// assert_eq!(Step::new(1, 1).symmetries().len(), 4);
+    /// assert_eq!(Step::new(0, 0).symmetries(), vec![Step::new(0, 0)]);
+    /// ```
+    pub fn symmetries(self) -> Vec<Step> {
+        self.images(Symmetry::All)
+    }
+
+    /// Returns the unique images of the step under `symmetry`.
+    /// The first image is the step itself.
+    /// # Example
+    /// ```
+    /// # use gridit::{Step, Symmetry};
+    /// let images = Step::new(1, 2).images(Symmetry::MirrorX);
+    /// assert_eq!(images, vec![Step::new(1, 2), Step::new(-1, 2)]);
+    /// ```
+    pub fn images(self, symmetry: Symmetry) -> Vec<Step> {
+        let mut images = Vec::new();
+        for transform in symmetry.transforms() {
+            let image = transform(self);
+            if !images.contains(&image) {
+                images.push(image);
+            }
+        }
+        images
+    }
+
+    // Takes the step from position `pos`, the axes in `wrap` wrap around `size`.
+    // Returns None if a not wrapping axis under- or overflows.
+    pub(crate) fn take_step_wrapping(
+        &self,
+        pos: Position,
+        (width, height): (usize, usize),
+        (wrap_x, wrap_y): (bool, bool),
+    ) -> Option<Position> {
+        let x = match wrap_x {
+            true => wrapping_add(pos.x, self.x, width),
+            false => pos.x.checked_add_signed(self.x)?,
+        };
+        let y = match wrap_y {
+            true => wrapping_add(pos.y, self.y, height),
+            false => pos.y.checked_add_signed(self.y)?,
+        };
         Some((x, y).into())
     }
 }
 
-// TODO Create a better impl for all Numbers T -> (T, T)
-impl From<(usize, usize)> for Step {
-    fn from((x, y): (usize, usize)) -> Self {
-        Step::new(x, y)
+/// A group of rotations and reflections of the square, which maps a step to its images.
+/// See [Step::images].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symmetry {
+    /// Only the step itself.
+    Identity,
+    /// The step and its reflection with [Step::reflect_x].
+    MirrorX,
+    /// The step and its reflection with [Step::reflect_y].
+    MirrorY,
+    /// The step, both reflections and the rotation by 180 degrees.
+    MirrorXY,
+    /// The step and its rotation by 180 degrees.
+    Rotations2,
+    /// The step rotated by 0, 90, 180 and 270 degrees.
+    Rotations4,
+    /// All 8 rotations and reflections of the square.
+    All,
+}
+
+impl Symmetry {
+    // Returns the transforms of the group, the first one is the identity.
+    pub(crate) fn transforms(self) -> Vec<fn(Step) -> Step> {
+        let rotations: [fn(Step) -> Step; 4] = [
+            |step| step,
+            Step::rotate90,
+            |step| -step,
+            |step| -step.rotate90(),
+        ];
+        let reflections: [fn(Step) -> Step; 4] = [
+            Step::reflect_x,
+            |step| step.reflect_x().rotate90(),
+            Step::reflect_y,
+            |step| -step.reflect_x().rotate90(),
+        ];
+        match self {
+            Symmetry::Identity => vec![rotations[0]],
+            Symmetry::MirrorX => vec![rotations[0], reflections[0]],
+            Symmetry::MirrorY => vec![rotations[0], reflections[2]],
+            Symmetry::MirrorXY => vec![rotations[0], reflections[0], reflections[2], rotations[2]],
+            Symmetry::Rotations2 => vec![rotations[0], rotations[2]],
+            Symmetry::Rotations4 => rotations.to_vec(),
+            Symmetry::All => rotations.iter().chain(&reflections).copied().collect(),
+        }
+    }
+}
+
+// Adds `step` to `n` and wraps the result into 0..size
+fn wrapping_add(n: usize, step: isize, size: usize) -> usize {
+    // size is at most isize::MAX since it is the length of a Vec
+    let step = step.rem_euclid(size as isize) as usize;
+    (n % size + step) % size
+}
+
+impl Add for Step {
+    type Output = Step;
+
+    /// Adds the steps.
+    /// # Panics
+    /// * if x or y overflows and overflow checks are enabled, like in debug builds.
+    ///   See [Step::checked_add] for the checked version.
+    fn add(self, rhs: Step) -> Self::Output {
+        Step::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Step {
+    fn add_assign(&mut self, rhs: Step) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Step {
+    type Output = Step;
+
+    /// Subtracts `rhs` from the step.
+    /// # Panics
+    /// * if x or y overflows and overflow checks are enabled, like in debug builds.
+    ///   See [Step::checked_sub] for the checked version.
+    fn sub(self, rhs: Step) -> Self::Output {
+        Step::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Step {
+    fn sub_assign(&mut self, rhs: Step) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Step {
+    type Output = Step;
+
+    /// Scales the step by `rhs`.
+    /// # Panics
+    /// * if x or y overflows and overflow checks are enabled, like in debug builds.
+    ///   See [Step::checked_mul] for the checked version.
+    fn mul(self, rhs: i32) -> Self::Output {
+        let rhs = rhs as isize;
+        Step::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Step {
+    type Output = Step;
+
+    /// Negates the step.
+    /// # Panics
+    /// * if x or y overflows and overflow checks are enabled, like in debug builds.
+    ///   See [Step::checked_neg] for the checked version.
+    fn neg(self) -> Self::Output {
+        Step::new(-self.x, -self.y)
+    }
+}
+
+impl Add<Step> for Position {
+    type Output = Option<Position>;
+
+    /// Adds the step to the position, returns `None` if the new position
+    /// would be negative or overflow.
+    fn add(self, rhs: Step) -> Self::Output {
+        Some(Position::new(
+            self.x.checked_add_signed(rhs.x)?,
+            self.y.checked_add_signed(rhs.y)?,
+        ))
+    }
+}
+
+impl Sub<Step> for Position {
+    type Output = Option<Position>;
+
+    /// Subtracts the step from the position, returns `None` if the new position
+    /// would be negative or overflow.
+    fn sub(self, rhs: Step) -> Self::Output {
+        Some(Position::new(
+            checked_sub_signed(self.x, rhs.x)?,
+            checked_sub_signed(self.y, rhs.y)?,
+        ))
+    }
+}
+
+fn checked_sub_signed(n: usize, rhs: isize) -> Option<usize> {
+    match rhs >= 0 {
+        true => n.checked_sub(rhs as usize),
+        false => n.checked_add(rhs.unsigned_abs()),
+    }
+}
+
+impl TryFrom<Step> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(step: Step) -> Result<Self, Self::Error> {
+        Ok(Position::new(
+            usize::try_from(step.x)?,
+            usize::try_from(step.y)?,
+        ))
+    }
+}
+
+impl TryFrom<Position> for Step {
+    type Error = TryFromIntError;
+
+    fn try_from(pos: Position) -> Result<Self, Self::Error> {
+        Ok(Step::new(isize::try_from(pos.x)?, isize::try_from(pos.y)?))
     }
 }
 
-// TODO Create a better impl for all Numbers T -> (T, T) where T: Neg or something likes this
 impl From<(isize, isize)> for Step {
     fn from((x, y): (isize, isize)) -> Self {
-        Step {
-            x: N::from_isize(x),
-            y: N::from_isize(y),
-        }
+        Step::new(x, y)
     }
 }
 
 impl From<(i32, i32)> for Step {
     fn from((x, y): (i32, i32)) -> Self {
-        Step {
-            x: N::from_i32(x),
-            y: N::from_i32(y),
-        }
+        Step::new(x as isize, y as isize)
+    }
+}
+
+impl From<Step> for (isize, isize) {
+    fn from(step: Step) -> Self {
+        (step.x, step.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_arithmetic() {
+        let step = Step::new(2, -1);
+        assert_eq!(step + Step::new(-3, 4), Step::new(-1, 3));
+        assert_eq!(step - Step::new(-3, 4), Step::new(5, -5));
+        assert_eq!(step * -3, Step::new(-6, 3));
+        assert_eq!(-step, Step::new(-2, 1));
+
+        let mut step = step;
+        step += Step::new(1, 1);
+        step -= Step::new(0, 2);
+        assert_eq!(step, Step::new(3, -2));
+    }
+
+    #[test]
+    fn checked_step_arithmetic() {
+        let step = Step::new(2, -1);
+        assert_eq!(
+            step.checked_add(Step::new(-3, 4)),
+            Some(step + Step::new(-3, 4))
+        );
+        assert_eq!(
+            step.checked_sub(Step::new(-3, 4)),
+            Some(step - Step::new(-3, 4))
+        );
+        assert_eq!(step.checked_mul(-3), Some(step * -3));
+        assert_eq!(step.checked_neg(), Some(-step));
+
+        let max = Step::new(0, isize::MAX);
+        assert_eq!(max.checked_add(Step::new(0, 1)), None);
+        assert_eq!(max.checked_sub(Step::new(0, -1)), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(max.checked_mul(-1), Some(Step::new(0, -isize::MAX)));
+        assert_eq!(Step::new(0, isize::MIN).checked_neg(), None);
+        assert_eq!(Step::new(0, isize::MIN).checked_mul(-1), None);
+    }
+
+    #[test]
+    fn position_plus_step_is_checked() {
+        let pos = Position::new(1, 1);
+        assert_eq!(pos + Step::new(-1, 2), Some(Position::new(0, 3)));
+        assert_eq!(pos + Step::new(-2, 0), None);
+        assert_eq!(pos - Step::new(-1, 1), Some(Position::new(2, 0)));
+        assert_eq!(pos - Step::new(0, 2), None);
+        assert_eq!(Position::new(usize::MAX, 0) + Step::new(1, 0), None);
+        assert_eq!(
+            Position::new(0, 0) - Step::new(isize::MIN, 0),
+            Some(Position::new(isize::MIN.unsigned_abs(), 0))
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Position::try_from(Step::new(3, 4)), Ok(Position::new(3, 4)));
+        assert!(Position::try_from(Step::new(3, -4)).is_err());
+        assert_eq!(Step::try_from(Position::new(3, 4)), Ok(Step::new(3, 4)));
+        assert!(Step::try_from(Position::new(usize::MAX, 0)).is_err());
+    }
+
+    #[test]
+    fn wrapping_steps() {
+        let step = Step::new(-7, 5);
+        let pos = step.take_step_wrapping(Position::new(1, 1), (3, 3), (true, true));
+        assert_eq!(pos, Some(Position::new(0, 0)));
+        let pos = step.take_step_wrapping(Position::new(1, 1), (3, 3), (false, true));
+        assert_eq!(pos, None);
+    }
+
+    #[test]
+    fn step_symmetries() {
+        let step = Step::new(2, -1);
+        assert_eq!(step.rotate90().rotate90(), -step);
+        assert_eq!(step.rotate90().rotate90().rotate90().rotate90(), step);
+        assert_eq!(step.reflect_x().reflect_y(), -step);
+
+        let mut knight = Step::new(1, 2).symmetries();
+        knight.sort_by_key(|step| (step.x, step.y));
+        assert_eq!(
+            knight,
+            vec![
+                Step::new(-2, -1),
+                Step::new(-2, 1),
+                Step::new(-1, -2),
+                Step::new(-1, 2),
+                Step::new(1, -2),
+                Step::new(1, 2),
+                Step::new(2, -1),
+                Step::new(2, 1),
+            ]
+        );
+
+        let wazir = Step::new(0, -1);
+        assert_eq!(
+            wazir.images(Symmetry::All),
+            wazir.images(Symmetry::Rotations4)
+        );
+        assert_eq!(wazir.images(Symmetry::MirrorX), vec![wazir]);
+        assert_eq!(wazir.images(Symmetry::MirrorXY).len(), 2);
+        assert_eq!(Step::new(1, 1).images(Symmetry::MirrorXY).len(), 4);
+        assert_eq!(Step::new(1, 2).images(Symmetry::Rotations2).len(), 2);
+        assert_eq!(
+            Step::new(1, 2).images(Symmetry::Identity),
+            vec![Step::new(1, 2)]
+        );
     }
 }
@@ -0,0 +1,154 @@
+use crate::error::GridError;
+use crate::grid::Grid;
+use std::fmt;
+
+impl<T> Grid<T> {
+    /// Parses a text map into a grid, every line is a row and `map` turns every char into a cell.
+    /// Lines at the start and end of `s` which are empty or only contain whitespace are ignored,
+    /// so the map can be written in an indented multi-line string literal.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from_str_map("
+    /// #.#
+    /// ..#
+    /// ", |c| c == '#').unwrap();
+    /// assert_eq!(grid, Grid::from(vec![true, false, true, false, false, true], 3, 2));
+    /// ```
+    /// # Errors
+    /// * [GridError::RaggedLine] if a line has a different length than the first line.
+    /// * [GridError::ZeroSize] if there are no lines.
+    pub fn from_str_map<F>(s: &str, mut map: F) -> Result<Self, GridError>
+    where
+        F: FnMut(char) -> T,
+    {
+        let lines: Vec<&str> = s.lines().collect();
+        let start = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(0);
+        let end = lines
+            .iter()
+            .rposition(|l| !l.trim().is_empty())
+            .map_or(0, |i| i + 1);
+        let lines = &lines[start..end];
+
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        let mut items = Vec::with_capacity(width * lines.len());
+        for (line, text) in lines.iter().enumerate() {
+            let len = text.chars().count();
+            if len != width {
+                return Err(GridError::RaggedLine {
+                    line: start + line,
+                    len,
+                    width,
+                });
+            }
+            items.extend(text.chars().map(&mut map));
+        }
+        Grid::try_from_vec(items, width, lines.len())
+    }
+
+    /// Returns a value which displays the grid as text, `map` turns every cell into a char.
+    /// Rows are separated by a newline, there is no newline after the last row.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![true, false, true, false, false, true], 3, 2);
+    /// let text = grid.display_with(|&wall| if wall { '#' } else { '.' }).to_string();
+    /// assert_eq!(text, "#.#\n..#");
+    /// ```
+    pub fn display_with<F>(&self, map: F) -> DisplayWith<'_, T, F>
+    where
+        F: Fn(&T) -> char,
+    {
+        DisplayWith { grid: self, map }
+    }
+}
+
+/// Displays a grid as text, created with [Grid::display_with].
+pub struct DisplayWith<'a, T, F> {
+    grid: &'a Grid<T>,
+    map: F,
+}
+
+impl<'a, T, F> fmt::Display for DisplayWith<'a, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.grid.items.chunks(self.grid.width).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", (self.map)(cell))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display_roundtrip() {
+        let map = "
+S..#
+.#..
+...E
+";
+        let grid = Grid::from_str_map(map, |c| c).unwrap();
+        assert_eq!(grid.size(), (4, 3));
+        assert_eq!(grid.get((3, 2)), Some(&'E'));
+        assert_eq!(grid.display_with(|&c| c).to_string(), map.trim());
+    }
+
+    #[test]
+    fn parse_indented_literal() {
+        let map = "
+            .#
+            #.
+            ";
+        let grid = Grid::from_str_map(map, |c| c).unwrap();
+        assert_eq!(grid.size(), (14, 2));
+        assert_eq!(grid.row(0).filter(|c| **c == ' ').count(), 12);
+
+        let grid = Grid::from_str_map("  \n.#\n#.\n    ", |c| c == '#').unwrap();
+        assert_eq!(grid, Grid::from(vec![false, true, true, false], 2, 2));
+        assert_eq!(
+            Grid::from_str_map(" \n \n", |c| c),
+            Err(GridError::ZeroSize {
+                width: 0,
+                height: 0
+            })
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Grid::from_str_map("..\n...\n..", |c| c),
+            Err(GridError::RaggedLine {
+                line: 1,
+                len: 3,
+                width: 2
+            })
+        );
+        assert_eq!(
+            Grid::from_str_map("\n\n", |c| c),
+            Err(GridError::ZeroSize {
+                width: 0,
+                height: 0
+            })
+        );
+        // empty lines in the middle are ragged lines
+        assert_eq!(
+            Grid::from_str_map("\n.\n\n.", |c| c),
+            Err(GridError::RaggedLine {
+                line: 2,
+                len: 0,
+                width: 1
+            })
+        );
+    }
+}
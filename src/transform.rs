@@ -0,0 +1,304 @@
+use crate::grid::{Grid, Position};
+
+impl<T> Grid<T> {
+    // Moves the cell at every index `i` to index `dest(i)` in place by following
+    // the cycles of the permutation, a bitset marks the cells already moved.
+    fn permute<F>(&mut self, dest: F)
+    where
+        F: Fn(usize) -> usize,
+    {
+        let mut moved = vec![0u64; self.items.len().div_ceil(64)];
+        for start in 0..self.items.len() {
+            if moved[start / 64] & (1 << (start % 64)) != 0 {
+                continue;
+            }
+            let mut idx = dest(start);
+            while idx != start {
+                self.items.swap(start, idx);
+                moved[idx / 64] |= 1 << (idx % 64);
+                idx = dest(idx);
+            }
+        }
+    }
+
+    /// Transposes the grid in place, the cell at (x, y) moves to (y, x).
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// grid.transpose();
+    /// assert_eq!(grid, Grid::from(vec![1, 4, 2, 5, 3, 6], 2, 3));
+    /// ```
+    pub fn transpose(&mut self) {
+        let (width, height) = (self.width, self.height);
+        if width == height {
+            for y in 0..height {
+                for x in y + 1..width {
+                    self.items.swap(y * width + x, x * width + y);
+                }
+            }
+        } else if width > 1 && height > 1 {
+            // the cell at (idx % width, idx / width) moves to (idx / width, idx % width)
+            self.permute(|idx| idx % width * height + idx / width);
+        }
+        self.width = height;
+        self.height = width;
+    }
+
+    /// Rotates the grid clockwise by 90 degrees in place.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// grid.rotate_cw();
+    /// assert_eq!(grid, Grid::from(vec![4, 1, 5, 2, 6, 3], 2, 3));
+    /// ```
+    pub fn rotate_cw(&mut self) {
+        self.transpose();
+        self.flip_horizontal();
+    }
+
+    /// Rotates the grid counterclockwise by 90 degrees in place.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// grid.rotate_ccw();
+    /// assert_eq!(grid, Grid::from(vec![3, 6, 2, 5, 1, 4], 2, 3));
+    /// ```
+    pub fn rotate_ccw(&mut self) {
+        self.transpose();
+        self.flip_vertical();
+    }
+
+    /// Rotates the grid by 180 degrees in place.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// grid.rotate_180();
+    /// assert_eq!(grid, Grid::from(vec![6, 5, 4, 3, 2, 1], 3, 2));
+    /// ```
+    pub fn rotate_180(&mut self) {
+        self.items.reverse();
+    }
+
+    /// Mirrors the grid in place, the left column becomes the right column.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// grid.flip_horizontal();
+    /// assert_eq!(grid, Grid::from(vec![3, 2, 1, 6, 5, 4], 3, 2));
+    /// ```
+    pub fn flip_horizontal(&mut self) {
+        self.items
+            .chunks_mut(self.width)
+            .for_each(|row| row.reverse());
+    }
+
+    /// Mirrors the grid in place, the top row becomes the bottom row.
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let mut grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// grid.flip_vertical();
+    /// assert_eq!(grid, Grid::from(vec![4, 5, 6, 1, 2, 3], 3, 2));
+    /// ```
+    pub fn flip_vertical(&mut self) {
+        self.items.reverse();
+        self.flip_horizontal();
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Creates a new `width` x `height` grid,
+    // where `source` returns the position in this grid of every new position.
+    fn remapped<F>(&self, width: usize, height: usize, source: F) -> Grid<T>
+    where
+        F: Fn(usize, usize) -> Position,
+    {
+        let mut items = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                items.push(self.get_unchecked(source(x, y)).clone());
+            }
+        }
+        Grid {
+            items,
+            width,
+            height,
+        }
+    }
+
+    /// Returns a transposed copy of the grid. See [Grid::transpose].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// assert_eq!(grid.transposed(), Grid::from(vec![1, 4, 2, 5, 3, 6], 2, 3));
+    /// ```
+    pub fn transposed(&self) -> Grid<T> {
+        self.remapped(self.height, self.width, |x, y| (y, x).into())
+    }
+
+    /// Returns a copy of the grid rotated clockwise by 90 degrees. See [Grid::rotate_cw].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// assert_eq!(grid.rotated_cw(), Grid::from(vec![4, 1, 5, 2, 6, 3], 2, 3));
+    /// ```
+    pub fn rotated_cw(&self) -> Grid<T> {
+        let height = self.height;
+        self.remapped(self.height, self.width, |x, y| (y, height - 1 - x).into())
+    }
+
+    /// Returns a copy of the grid rotated counterclockwise by 90 degrees. See [Grid::rotate_ccw].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// assert_eq!(grid.rotated_ccw(), Grid::from(vec![3, 6, 2, 5, 1, 4], 2, 3));
+    /// ```
+    pub fn rotated_ccw(&self) -> Grid<T> {
+        let width = self.width;
+        self.remapped(self.height, self.width, |x, y| (width - 1 - y, x).into())
+    }
+
+    /// Returns a copy of the grid rotated by 180 degrees. See [Grid::rotate_180].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// assert_eq!(grid.rotated_180(), Grid::from(vec![6, 5, 4, 3, 2, 1], 3, 2));
+    /// ```
+    pub fn rotated_180(&self) -> Grid<T> {
+        let (width, height) = (self.width, self.height);
+        self.remapped(width, height, |x, y| (width - 1 - x, height - 1 - y).into())
+    }
+
+    /// Returns a horizontally mirrored copy of the grid. See [Grid::flip_horizontal].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// assert_eq!(grid.flipped_horizontal(), Grid::from(vec![3, 2, 1, 6, 5, 4], 3, 2));
+    /// ```
+    pub fn flipped_horizontal(&self) -> Grid<T> {
+        let width = self.width;
+        self.remapped(width, self.height, |x, y| (width - 1 - x, y).into())
+    }
+
+    /// Returns a vertically mirrored copy of the grid. See [Grid::flip_vertical].
+    /// # Example
+    /// ```
+    /// # use gridit::Grid;
+    /// let grid = Grid::from(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    /// assert_eq!(grid.flipped_vertical(), Grid::from(vec![4, 5, 6, 1, 2, 3], 3, 2));
+    /// ```
+    pub fn flipped_vertical(&self) -> Grid<T> {
+        let height = self.height;
+        self.remapped(self.width, height, |x, y| (x, height - 1 - y).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3
+    // 4, 5, 6, 7
+    fn grid_4x2() -> Grid<usize> {
+        Grid {
+            width: 4,
+            height: 2,
+            items: (0..8).collect(),
+        }
+    }
+
+    #[test]
+    fn rotate_cw_four_times() {
+        let mut grid = grid_4x2();
+        grid.rotate_cw();
+        assert_eq!(grid.size(), (2, 4));
+        assert_eq!(grid.items, vec![4, 0, 5, 1, 6, 2, 7, 3]);
+        grid.rotate_cw();
+        assert_eq!(grid, grid_4x2().rotated_180());
+        grid.rotate_cw();
+        assert_eq!(grid, grid_4x2().rotated_ccw());
+        grid.rotate_cw();
+        assert_eq!(grid, grid_4x2());
+    }
+
+    #[test]
+    fn rotate_ccw_is_inverse_of_cw() {
+        let mut grid = grid_4x2();
+        grid.rotate_ccw();
+        assert_eq!(grid.size(), (2, 4));
+        assert_eq!(grid.items, vec![3, 7, 2, 6, 1, 5, 0, 4]);
+        grid.rotate_cw();
+        assert_eq!(grid, grid_4x2());
+    }
+
+    #[test]
+    fn transpose_twice() {
+        let mut grid = grid_4x2();
+        grid.transpose();
+        assert_eq!(grid.items, vec![0, 4, 1, 5, 2, 6, 3, 7]);
+        assert_eq!(grid.get((1, 3)), Some(&7));
+        grid.transpose();
+        assert_eq!(grid, grid_4x2());
+    }
+
+    #[test]
+    fn in_place_matches_copies() {
+        for (width, height) in [(1, 1), (1, 5), (5, 1), (3, 3), (4, 4), (5, 3), (2, 7)] {
+            let grid = |width, height| Grid {
+                width,
+                height,
+                items: (0..width * height).collect::<Vec<_>>(),
+            };
+            let copy = grid(width, height);
+            let mut transposed = grid(width, height);
+            transposed.transpose();
+            assert_eq!(transposed, copy.transposed(), "{}x{}", width, height);
+            let mut cw = grid(width, height);
+            cw.rotate_cw();
+            assert_eq!(cw, copy.rotated_cw(), "{}x{}", width, height);
+            let mut ccw = grid(width, height);
+            ccw.rotate_ccw();
+            assert_eq!(ccw, copy.rotated_ccw(), "{}x{}", width, height);
+        }
+
+        // The in place transformations do not need T: Clone
+        #[derive(Debug, PartialEq)]
+        struct Cell(usize);
+        let mut grid = Grid {
+            width: 3,
+            height: 2,
+            items: (0..6).map(Cell).collect(),
+        };
+        grid.rotate_cw();
+        let items: Vec<usize> = grid.items.iter().map(|cell| cell.0).collect();
+        assert_eq!(items, vec![3, 0, 4, 1, 5, 2]);
+    }
+
+    #[test]
+    fn flips_match_copies() {
+        let mut grid = grid_4x2();
+        grid.flip_horizontal();
+        assert_eq!(grid.items, vec![3, 2, 1, 0, 7, 6, 5, 4]);
+        assert_eq!(grid, grid_4x2().flipped_horizontal());
+
+        let mut grid = grid_4x2();
+        grid.flip_vertical();
+        assert_eq!(grid.items, vec![4, 5, 6, 7, 0, 1, 2, 3]);
+        assert_eq!(grid, grid_4x2().flipped_vertical());
+
+        let mut grid = grid_4x2();
+        grid.flip_horizontal();
+        grid.flip_vertical();
+        assert_eq!(grid, grid_4x2().rotated_180());
+    }
+}
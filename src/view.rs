@@ -0,0 +1,1085 @@
+use crate::grid::{cells_mut, Grid, Position, Rect};
+use crate::iter::*;
+use crate::neighborhood::Neighborhood;
+use crate::pattern::Pattern;
+use crate::step::Step;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+/// The axes on which a view wraps around, like on a cylinder or torus.
+/// Stepping over the border of a wrapping axis continues on the opposite side.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Wrap {
+    /// No axis wraps, the border ends the view.
+    #[default]
+    None,
+    /// The x axis wraps, the left and right border are connected.
+    Horizontal,
+    /// The y axis wraps, the top and bottom border are connected.
+    Vertical,
+    /// Both axes wrap, the view is a torus.
+    Both,
+}
+
+impl Wrap {
+    // Returns if the x and y axis wrap
+    pub(crate) fn axes(&self) -> (bool, bool) {
+        match self {
+            Wrap::None => (false, false),
+            Wrap::Horizontal => (true, false),
+            Wrap::Vertical => (false, true),
+            Wrap::Both => (true, true),
+        }
+    }
+}
+
+/// A borrowed rectangular region of a [Grid].
+/// All positions are local to the view, position (0,0) is the top left corner of the view.
+/// Use [GridView::to_parent] to get the position in the parent grid.
+///
+/// With [GridView::with_wrap] the view wraps around its borders,
+/// which affects [GridView::neighbors] and [GridView::pattern].
+pub struct GridView<'a, T> {
+    pub(crate) cells: ViewCells<'a, T>,
+    pub(crate) rect: Rect,
+    pub(crate) wrap: Wrap,
+}
+
+// Implemented by hand, since derive would require T: Clone
+impl<'a, T> Clone for GridView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for GridView<'a, T> {}
+
+// The cells a view borrows
+pub(crate) enum ViewCells<'a, T> {
+    // the whole parent grid
+    Grid(&'a Grid<T>),
+    // the rows of a mutable view, the first cell is at `origin` in the parent grid
+    Rows {
+        rows: &'a [&'a mut [T]],
+        origin: Position,
+    },
+}
+
+impl<'a, T> Clone for ViewCells<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for ViewCells<'a, T> {}
+
+impl<'a, T> ViewCells<'a, T> {
+    // Returns the cell at the position `pos` in the parent grid
+    fn get(&self, pos: Position) -> &'a T {
+        match *self {
+            ViewCells::Grid(grid) => grid.get_unchecked(pos),
+            ViewCells::Rows { rows, origin } => &rows[pos.y - origin.y][pos.x - origin.x],
+        }
+    }
+
+    // Returns `len` cells of row `pos.y` starting at `pos.x`,
+    // `pos` is a position in the parent grid.
+    fn row(&self, pos: Position, len: usize) -> &'a [T] {
+        match *self {
+            ViewCells::Grid(grid) => {
+                let start_idx = grid.translate(pos);
+                &grid.items[start_idx..start_idx + len]
+            }
+            ViewCells::Rows { rows, origin } => {
+                let start_x = pos.x - origin.x;
+                &rows[pos.y - origin.y][start_x..start_x + len]
+            }
+        }
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// Returns the rectangle of the view in the parent grid.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Returns the axes on which the view wraps around.
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    /// Returns the view wrapping around the axes `wrap`.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect, Wrap};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view(Rect::new((0, 0), 2, 2)).with_wrap(Wrap::Horizontal);
+    /// assert_eq!(view.neighbors((0, 0)).collect::<Vec<_>>(), vec![&1, &4, &3]);
+    /// ```
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Returns the width and height of the view.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let grid = Grid::new(4, 4, 0);
+    /// let view = grid.view(Rect::new((1, 1), 3, 2));
+    /// assert_eq!(view.size(), (3, 2));
+    /// ```
+    pub fn size(&self) -> (usize, usize) {
+        (self.rect.width, self.rect.height)
+    }
+
+    /// Checks if the local position `pos` is in bounds of the view.
+    #[inline]
+    pub fn is_bounds<P: Into<Position>>(&self, pos: P) -> bool {
+        let pos = pos.into();
+        pos.x < self.rect.width && pos.y < self.rect.height
+    }
+
+    /// Translates the local position `pos` to the position in the parent grid.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect, Position};
+    /// let grid = Grid::new(4, 4, 0);
+    /// let view = grid.view(Rect::new((1, 2), 2, 2));
+    /// assert_eq!(view.to_parent((1, 0)), Position::new(2, 2));
+    /// ```
+    pub fn to_parent<P: Into<Position>>(&self, pos: P) -> Position {
+        let pos = pos.into();
+        (self.rect.x + pos.x, self.rect.y + pos.y).into()
+    }
+
+    /// Returns a reference to an element at the local position `pos`
+    /// or `None`, if `pos` is out of bounds of the view.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view(Rect::new((1, 1), 2, 2));
+    /// assert_eq!(view.get((0, 0)), Some(&4));
+    /// assert_eq!(view.get((2, 0)), None);
+    /// ```
+    pub fn get<P: Into<Position>>(&self, pos: P) -> Option<&'a T> {
+        let pos = pos.into();
+        if self.is_bounds(pos) {
+            return Some(self.get_unchecked(pos));
+        }
+        None
+    }
+
+    #[inline]
+    pub(crate) fn get_unchecked(&self, pos: Position) -> &'a T {
+        self.cells.get(self.to_parent(pos))
+    }
+
+    // Returns if at least one axis wraps
+    pub(crate) fn wraps(&self) -> bool {
+        self.wrap != Wrap::None
+    }
+
+    // Takes `step` from position `pos` and wraps around the wrapping axes.
+    // Returns None if the new position is outside the view.
+    pub(crate) fn step(&self, pos: Position, step: Step) -> Option<Position> {
+        let pos = step.take_step_wrapping(pos, self.size(), self.wrap.axes())?;
+        if self.is_bounds(pos) {
+            return Some(pos);
+        }
+        None
+    }
+
+    // Returns if two of the distinct `steps` can lead to the same cell,
+    // which only happens on a wrapping axis not longer than the steps span.
+    fn wraps_within(&self, steps: &[Step]) -> bool {
+        let span = |axis: fn(&Step) -> isize| {
+            let (min, max) = steps
+                .iter()
+                .map(axis)
+                .fold((0, 0), |(min, max), d| (d.min(min), d.max(max)));
+            max.abs_diff(min)
+        };
+        let (wrap_x, wrap_y) = self.wrap.axes();
+        (wrap_x && self.rect.width <= span(|step| step.x))
+            || (wrap_y && self.rect.height <= span(|step| step.y))
+    }
+
+    // Wraps position `pos` around the wrapping axes.
+    // Returns None if the position is outside the view.
+    pub(crate) fn wrap_position(&self, pos: Position) -> Option<Position> {
+        self.step(pos, Step::new(0, 0))
+    }
+
+    /// Creates a view of the region `rect`, which is local to this view.
+    /// The new view does not wrap around.
+    /// # Panics
+    /// * if `rect` is empty or not inside the view.
+    pub fn view(&self, rect: Rect) -> GridView<'a, T> {
+        assert_rect_inside(rect, self.size());
+        GridView {
+            cells: self.cells,
+            rect: Rect {
+                x: self.rect.x + rect.x,
+                y: self.rect.y + rect.y,
+                ..rect
+            },
+            wrap: Wrap::None,
+        }
+    }
+
+    /// Creates an iterator which yields references of every element in row `y` of the view.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view(Rect::new((1, 1), 2, 2));
+    /// assert_eq!(view.row(1).collect::<Vec<_>>(), vec![&7, &8]);
+    /// ```
+    /// # Panics
+    /// * if the row is out of bounds.
+    pub fn row(&self, y: usize) -> RowIter<'a, T> {
+        assert!(self.is_bounds((0, y)));
+        let row = self.cells.row(self.to_parent((0, y)), self.rect.width);
+
+        RowIter {
+            row_iter: row.iter(),
+            idx: y,
+            x: 0,
+        }
+    }
+
+    /// Creates an iterator which yields references of every element in column `x` of the view.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view(Rect::new((1, 1), 2, 2));
+    /// assert_eq!(view.column(0).collect::<Vec<_>>(), vec![&4, &7]);
+    /// ```
+    /// # Panics
+    /// * if the column is out of bounds.
+    pub fn column(&self, x: usize) -> ColumnIter<'a, T> {
+        assert!(self.is_bounds((x, 0)));
+        ColumnIter {
+            row_idx: 0,
+            end_row: self.rect.height,
+            col_idx: x,
+            grid: *self,
+        }
+    }
+
+    // Returns every valid neighbor position of x,y in `neighborhood`.
+    // On wrapping axes of small views different steps can lead to the same cell,
+    // every neighbor position is only returned once and never the position itself.
+    pub(crate) fn neighbor_positions(
+        &self,
+        pos: Position,
+        neighborhood: &Neighborhood,
+    ) -> Vec<Position> {
+        let steps = neighborhood.steps();
+        let mut valid_positions: Vec<Position> = Vec::with_capacity(steps.len());
+        for step in steps {
+            if let Some(neighbor) = self.step(pos, *step) {
+                if neighbor != pos {
+                    valid_positions.push(neighbor);
+                }
+            }
+        }
+        if self.wraps_within(steps) {
+            let mut seen = HashSet::with_capacity(valid_positions.len());
+            valid_positions.retain(|neighbor| seen.insert(*neighbor));
+        }
+
+        valid_positions
+    }
+
+    /// Creates an iterator which yields references of every neighbor element
+    /// of the local position `pos`. Neighbors outside of the view are not yielded,
+    /// unless the view wraps around.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view(Rect::new((1, 1), 2, 2));
+    /// assert_eq!(view.neighbors((0, 0)).collect::<Vec<_>>(), vec![&5, &7, &8]);
+    /// ```
+    /// # Panics
+    /// * if `pos` is out of bounds of the view.
+    pub fn neighbors<P: Into<Position>>(&self, pos: P) -> NeighborIter<'a, T> {
+        self.neighbors_with(pos, &Neighborhood::moore())
+    }
+
+    /// Creates an iterator which yields references of every element in `neighborhood`
+    /// of the local position `pos`. See [Grid::neighbors_with].
+    /// # Panics
+    /// * if `pos` is out of bounds of the view.
+    pub fn neighbors_with<P: Into<Position>>(
+        &self,
+        pos: P,
+        neighborhood: &Neighborhood,
+    ) -> NeighborIter<'a, T> {
+        let pos = pos.into();
+        assert!(self.is_bounds(pos));
+        let positions = self.neighbor_positions(pos, neighborhood);
+        NeighborIter {
+            positions: PositionList::new(positions),
+            grid: *self,
+        }
+    }
+
+    /// Creates an iterator which yields references of every element of pattern starting
+    /// at the local position `pos`. The pattern ends at the border of the view.
+    /// See [Pattern] more details.
+    ///
+    /// If the view wraps around, steps and jumps over the border of a wrapping axis
+    /// continue on the opposite side. A straight ray with [Repeat::TillEnd](crate::pattern::Repeat::TillEnd),
+    /// e.g. [DirectionPattern](crate::pattern::DirectionPattern), ends before it would return to `pos`.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// # use gridit::pattern::{DirectionPattern, Repeat};
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view(Rect::new((0, 0), 2, 3));
+    /// let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd);
+    /// assert_eq!(view.pattern((0, 1), pattern).collect::<Vec<_>>(), vec![&4]);
+    /// ```
+    pub fn pattern<P, Pat>(&self, pos: P, pattern: Pat) -> PatternIter<'a, T>
+    where
+        P: Into<Position>,
+        Pat: Pattern + 'static,
+    {
+        PatternIter::new(*self, pos.into(), Box::new(pattern))
+    }
+
+    // Returns the positions of pattern starting at position `pos`,
+    // only the first visit of every position is kept.
+    // Stops once every cell of the view was visited, even if the pattern goes on.
+    pub(crate) fn distinct_pattern_positions<Pat>(
+        &self,
+        pos: Position,
+        pattern: Pat,
+    ) -> Vec<Position>
+    where
+        Pat: Pattern + 'static,
+    {
+        let mut visited = HashSet::new();
+        self.pattern(pos, pattern)
+            .grid_positions()
+            .map(|(pos, _)| pos)
+            .filter(|pos| visited.insert(*pos))
+            .take(self.rect.width * self.rect.height)
+            .collect()
+    }
+}
+
+/// A mutably borrowed rectangular region of a [Grid].
+/// All positions are local to the view, position (0,0) is the top left corner of the view.
+/// Use [GridViewMut::to_parent] to get the position in the parent grid.
+///
+/// A view can be split into disjoint views with [GridViewMut::split_at_row]
+/// and [GridViewMut::split_at_column].
+///
+/// With [GridViewMut::with_wrap] the view wraps around its borders,
+/// which affects [GridViewMut::neighbors_mut] and [GridViewMut::pattern_mut].
+pub struct GridViewMut<'a, T> {
+    pub(crate) rows: Vec<&'a mut [T]>,
+    pub(crate) rect: Rect,
+    pub(crate) wrap: Wrap,
+}
+
+impl<'a, T> GridViewMut<'a, T> {
+    /// Returns the rectangle of the view in the parent grid.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Returns the axes on which the view wraps around.
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    /// Returns the view wrapping around the axes `wrap`.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect, Wrap};
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// let mut view = grid.view_mut(Rect::new((0, 0), 3, 3)).with_wrap(Wrap::Both);
+    /// view.neighbors_mut((0, 0)).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid.iter().filter(|cell| **cell == 1).count(), 8);
+    /// ```
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Returns the width and height of the view.
+    pub fn size(&self) -> (usize, usize) {
+        (self.rect.width, self.rect.height)
+    }
+
+    /// Checks if the local position `pos` is in bounds of the view.
+    #[inline]
+    pub fn is_bounds<P: Into<Position>>(&self, pos: P) -> bool {
+        let pos = pos.into();
+        pos.x < self.rect.width && pos.y < self.rect.height
+    }
+
+    /// Translates the local position `pos` to the position in the parent grid.
+    pub fn to_parent<P: Into<Position>>(&self, pos: P) -> Position {
+        let pos = pos.into();
+        (self.rect.x + pos.x, self.rect.y + pos.y).into()
+    }
+
+    /// Returns a reference to an element at the local position `pos`
+    /// or `None`, if `pos` is out of bounds of the view.
+    pub fn get<P: Into<Position>>(&self, pos: P) -> Option<&T> {
+        let pos = pos.into();
+        if self.is_bounds(pos) {
+            return Some(&self.rows[pos.y][pos.x]);
+        }
+        None
+    }
+
+    /// Returns a mutable reference to an element at the local position `pos`
+    /// or `None`, if `pos` is out of bounds of the view.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::from((0..9).collect(), 3, 3);
+    /// let mut view = grid.view_mut(Rect::new((1, 1), 2, 2));
+    /// *view.get_mut((1, 1)).unwrap() = 42;
+    /// assert_eq!(grid.get((2, 2)), Some(&42));
+    /// ```
+    pub fn get_mut<P: Into<Position>>(&mut self, pos: P) -> Option<&mut T> {
+        let pos = pos.into();
+        if self.is_bounds(pos) {
+            return Some(&mut self.rows[pos.y][pos.x]);
+        }
+        None
+    }
+
+    /// Splits the view into two disjoint views at row `y`.
+    /// The first view contains the rows `0..y`, the second the rows `y..height`.
+    /// The new views do not wrap around.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::new(4, 4, 0);
+    /// let view = grid.view_mut(Rect::new((0, 0), 4, 4));
+    /// let (mut top, mut bottom) = view.split_at_row(1);
+    /// top.row_mut(0).for_each(|cell| *cell = 1);
+    /// bottom.row_mut(0).for_each(|cell| *cell = 2);
+    /// assert_eq!(grid.get((3, 0)), Some(&1));
+    /// assert_eq!(grid.get((3, 1)), Some(&2));
+    /// ```
+    /// # Panics
+    /// * if one of the views would be empty.
+    pub fn split_at_row(self, y: usize) -> (GridViewMut<'a, T>, GridViewMut<'a, T>) {
+        assert!(
+            y > 0 && y < self.rect.height,
+            "split row {} does not split the view",
+            y
+        );
+        let mut top_rows = self.rows;
+        let bottom_rows = top_rows.split_off(y);
+        let top = GridViewMut {
+            rows: top_rows,
+            rect: Rect {
+                height: y,
+                ..self.rect
+            },
+            wrap: Wrap::None,
+        };
+        let bottom = GridViewMut {
+            rows: bottom_rows,
+            rect: Rect {
+                y: self.rect.y + y,
+                height: self.rect.height - y,
+                ..self.rect
+            },
+            wrap: Wrap::None,
+        };
+        (top, bottom)
+    }
+
+    /// Splits the view into two disjoint views at column `x`.
+    /// The first view contains the columns `0..x`, the second the columns `x..width`.
+    /// The new views do not wrap around.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::new(4, 4, 0);
+    /// let view = grid.view_mut(Rect::new((0, 0), 4, 4));
+    /// let (left, right) = view.split_at_column(2);
+    /// let (mut top_left, _) = left.split_at_row(2);
+    /// let (_, mut bottom_right) = right.split_at_row(2);
+    /// top_left.set((1, 1), 1);
+    /// bottom_right.set((0, 0), 2);
+    /// assert_eq!(grid.get((1, 1)), Some(&1));
+    /// assert_eq!(grid.get((2, 2)), Some(&2));
+    /// ```
+    /// # Panics
+    /// * if one of the views would be empty.
+    pub fn split_at_column(self, x: usize) -> (GridViewMut<'a, T>, GridViewMut<'a, T>) {
+        assert!(
+            x > 0 && x < self.rect.width,
+            "split column {} does not split the view",
+            x
+        );
+        let (left_rows, right_rows) = self.rows.into_iter().map(|row| row.split_at_mut(x)).unzip();
+        let left = GridViewMut {
+            rows: left_rows,
+            rect: Rect {
+                width: x,
+                ..self.rect
+            },
+            wrap: Wrap::None,
+        };
+        let right = GridViewMut {
+            rows: right_rows,
+            rect: Rect {
+                x: self.rect.x + x,
+                width: self.rect.width - x,
+                ..self.rect
+            },
+            wrap: Wrap::None,
+        };
+        (left, right)
+    }
+
+    /// Sets the value at the local position `pos`.
+    /// Returns None if `pos` is out of bounds of the view,
+    /// or () otherwise.
+    pub fn set<P: Into<Position>>(&mut self, pos: P, value: T) -> Option<()> {
+        *self.get_mut(pos)? = value;
+        Some(())
+    }
+
+    /// Creates an iterator which yields references of every element in row `y` of the view.
+    /// # Panics
+    /// * if the row is out of bounds.
+    pub fn row(&self, y: usize) -> RowIter<'_, T> {
+        assert!(self.is_bounds((0, y)));
+        RowIter {
+            row_iter: self.rows[y].iter(),
+            idx: y,
+            x: 0,
+        }
+    }
+
+    /// Creates an iterator which yields mutable references of every element in row `y` of the view.
+    /// # Panics
+    /// * if the row is out of bounds.
+    pub fn row_mut(&mut self, y: usize) -> RowIterMut<'_, T> {
+        assert!(self.is_bounds((0, y)));
+        RowIterMut {
+            row_iter: self.rows[y].iter_mut(),
+            idx: y,
+            x: 0,
+        }
+    }
+
+    /// Creates an iterator which yields references of every element in column `x` of the view.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view_mut(Rect::new((1, 1), 2, 2));
+    /// assert_eq!(view.column(1).collect::<Vec<_>>(), vec![&5, &8]);
+    /// ```
+    /// # Panics
+    /// * if the column is out of bounds.
+    pub fn column(&self, x: usize) -> ColumnIter<'_, T> {
+        self.as_view().column(x)
+    }
+
+    /// Creates an iterator which yields mutable references of every element in column `x` of the view.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::from((0..9).collect(), 3, 3);
+    /// let mut view = grid.view_mut(Rect::new((1, 1), 2, 2));
+    /// view.column_mut(1).for_each(|cell| *cell = 0);
+    /// assert_eq!(grid.column(2).collect::<Vec<_>>(), vec![&2, &0, &0]);
+    /// ```
+    /// # Panics
+    /// * if the column is out of bounds.
+    pub fn column_mut(&mut self, x: usize) -> ColumnIterMut<'_, T, ViewColumnCellsMut<'_, 'a, T>> {
+        assert!(self.is_bounds((x, 0)));
+        let cells = ViewColumnCellsMut {
+            rows: self.rows.iter_mut(),
+            col_idx: x,
+        };
+        ColumnIterMut {
+            cells,
+            col_idx: x,
+            row_idx: 0,
+            end_row: self.rect.height,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates an iterator which yields references of every neighbor element
+    /// of the local position `pos`. Neighbors outside of the view are not yielded,
+    /// unless the view wraps around.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view_mut(Rect::new((1, 1), 2, 2));
+    /// assert_eq!(view.neighbors((0, 0)).collect::<Vec<_>>(), vec![&5, &7, &8]);
+    /// ```
+    /// # Panics
+    /// * if `pos` is out of bounds of the view.
+    pub fn neighbors<P: Into<Position>>(&self, pos: P) -> NeighborIter<'_, T> {
+        self.as_view().neighbors(pos)
+    }
+
+    /// Creates an iterator which yields references of every element in `neighborhood`
+    /// of the local position `pos`. See [Grid::neighbors_with].
+    /// # Panics
+    /// * if `pos` is out of bounds of the view.
+    pub fn neighbors_with<P: Into<Position>>(
+        &self,
+        pos: P,
+        neighborhood: &Neighborhood,
+    ) -> NeighborIter<'_, T> {
+        self.as_view().neighbors_with(pos, neighborhood)
+    }
+
+    /// Creates an iterator which yields mutable references of every neighbor element
+    /// of the local position `pos`. Neighbors outside of the view are not yielded,
+    /// unless the view wraps around.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// let mut view = grid.view_mut(Rect::new((0, 0), 2, 2));
+    /// view.neighbors_mut((0, 0)).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid.iter().filter(|cell| **cell == 1).count(), 3);
+    /// ```
+    /// # Panics
+    /// * if `pos` is out of bounds of the view.
+    pub fn neighbors_mut<P: Into<Position>>(&mut self, pos: P) -> NeighborIterMut<'_, T> {
+        self.neighbors_with_mut(pos, &Neighborhood::moore())
+    }
+
+    /// Creates an iterator which yields mutable references of every element in `neighborhood`
+    /// of the local position `pos`. See [Grid::neighbors_with_mut].
+    /// # Panics
+    /// * if `pos` is out of bounds of the view.
+    pub fn neighbors_with_mut<P: Into<Position>>(
+        &mut self,
+        pos: P,
+        neighborhood: &Neighborhood,
+    ) -> NeighborIterMut<'_, T> {
+        let pos = pos.into();
+        assert!(self.is_bounds(pos));
+        let positions = self.as_view().neighbor_positions(pos, neighborhood);
+        let cells = self.cells_mut(&positions);
+        NeighborIterMut {
+            positions: PositionList::new(positions),
+            cells: cells.into_iter(),
+        }
+    }
+
+    /// Creates an iterator which yields references of every element of pattern starting
+    /// at the local position `pos`. The pattern ends at the border of the view,
+    /// see [GridView::pattern].
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// # use gridit::pattern::{DirectionPattern, Repeat};
+    /// let mut grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view_mut(Rect::new((0, 0), 2, 3));
+    /// let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd);
+    /// assert_eq!(view.pattern((0, 1), pattern).collect::<Vec<_>>(), vec![&4]);
+    /// ```
+    pub fn pattern<P, Pat>(&self, pos: P, pattern: Pat) -> PatternIter<'_, T>
+    where
+        P: Into<Position>,
+        Pat: Pattern + 'static,
+    {
+        self.as_view().pattern(pos, pattern)
+    }
+
+    /// Creates an iterator which yields mutable references of every element of pattern starting
+    /// at the local position `pos`. The pattern ends at the border of the view.
+    /// Like [Grid::pattern_mut] every cell is only yielded the first time it is visited,
+    /// and the pattern is walked when the iterator is created.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// # use gridit::pattern::{DirectionPattern, Repeat};
+    /// let mut grid = Grid::new(4, 1, 0);
+    /// let mut view = grid.view_mut(Rect::new((0, 0), 3, 1));
+    /// let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd);
+    /// view.pattern_mut((0, 0), pattern).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![0, 1, 1, 0], 4, 1));
+    /// ```
+    pub fn pattern_mut<P, Pat>(&mut self, pos: P, pattern: Pat) -> PatternIterMut<'_, T>
+    where
+        P: Into<Position>,
+        Pat: Pattern + 'static,
+    {
+        let positions = self
+            .as_view()
+            .distinct_pattern_positions(pos.into(), pattern);
+        let cells = self.cells_mut(&positions);
+        PatternIterMut {
+            positions: PositionList::new(positions),
+            cells: cells.into_iter(),
+        }
+    }
+
+    // A read-only view of the same region, which wraps around like this view
+    fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            cells: ViewCells::Rows {
+                rows: &self.rows,
+                origin: (self.rect.x, self.rect.y).into(),
+            },
+            rect: self.rect,
+            wrap: self.wrap,
+        }
+    }
+
+    fn cells_mut(&mut self, positions: &[Position]) -> Vec<&mut T> {
+        cells_mut(self.rows.iter_mut().map(|row| &mut **row), positions)
+    }
+}
+
+fn assert_rect_inside(rect: Rect, (width, height): (usize, usize)) {
+    assert!(
+        rect.width > 0 && rect.height > 0,
+        "rect width and height can not be zero"
+    );
+    assert!(
+        rect.x.checked_add(rect.width).is_some_and(|r| r <= width)
+            && rect.y.checked_add(rect.height).is_some_and(|b| b <= height),
+        "rect is out of bounds"
+    );
+}
+
+impl<T> Grid<T> {
+    // A view of the whole grid
+    pub(crate) fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            cells: ViewCells::Grid(self),
+            rect: Rect::new((0, 0), self.width, self.height),
+            wrap: Wrap::None,
+        }
+    }
+
+    /// Creates a view of the region `rect` of the grid.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// // 0, 1, 2
+    /// // 3, 4, 5
+    /// // 6, 7, 8
+    /// let grid = Grid::from((0..9).collect(), 3, 3);
+    /// let view = grid.view(Rect::new((1, 1), 2, 2));
+    /// assert_eq!(view.get((0, 0)), Some(&4));
+    /// assert_eq!(view.neighbors((1, 1)).count(), 3);
+    /// ```
+    /// # Panics
+    /// * if `rect` is empty or not inside the grid.
+    pub fn view(&self, rect: Rect) -> GridView<'_, T> {
+        assert_rect_inside(rect, self.size());
+        GridView {
+            cells: ViewCells::Grid(self),
+            rect,
+            wrap: Wrap::None,
+        }
+    }
+
+    /// Creates a mutable view of the region `rect` of the grid.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Rect};
+    /// let mut grid = Grid::new(3, 3, 0);
+    /// let mut view = grid.view_mut(Rect::new((1, 1), 2, 2));
+    /// view.row_mut(0).for_each(|cell| *cell = 1);
+    /// assert_eq!(grid.row(1).collect::<Vec<_>>(), vec![&0, &1, &1]);
+    /// ```
+    /// # Panics
+    /// * if `rect` is empty or not inside the grid.
+    pub fn view_mut(&mut self, rect: Rect) -> GridViewMut<'_, T> {
+        assert_rect_inside(rect, self.size());
+        let rows = self
+            .items
+            .chunks_mut(self.width)
+            .skip(rect.y)
+            .take(rect.height)
+            .map(|row| &mut row[rect.x..rect.x + rect.width])
+            .collect();
+        GridViewMut {
+            rows,
+            rect,
+            wrap: Wrap::None,
+        }
+    }
+
+    /// Creates a view of the whole grid which wraps around the axes `wrap`.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Wrap};
+    /// # use gridit::pattern::{DirectionPattern, Repeat};
+    /// // 0, 1, 2
+    /// // 3, 4, 5
+    /// let grid = Grid::from((0..6).collect(), 3, 2);
+    /// let torus = grid.wrapping(Wrap::Both);
+    /// assert_eq!(torus.neighbors((0, 0)).count(), 5);
+    ///
+    /// let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd);
+    /// let ray: Vec<_> = torus.pattern((1, 1), pattern).collect();
+    /// assert_eq!(ray, vec![&5, &3]);
+    /// ```
+    pub fn wrapping(&self, wrap: Wrap) -> GridView<'_, T> {
+        self.as_view().with_wrap(wrap)
+    }
+
+    /// Creates a mutable view of the whole grid which wraps around the axes `wrap`.
+    /// # Example
+    /// ```
+    /// # use gridit::{Grid, Wrap};
+    /// let mut grid = Grid::new(4, 1, 0);
+    /// grid.wrapping_mut(Wrap::Horizontal)
+    ///     .neighbors_mut((0, 0))
+    ///     .for_each(|cell| *cell = 1);
+    /// assert_eq!(grid, Grid::from(vec![0, 1, 0, 1], 4, 1));
+    /// ```
+    pub fn wrapping_mut(&mut self, wrap: Wrap) -> GridViewMut<'_, T> {
+        let rect = Rect::new((0, 0), self.width, self.height);
+        self.view_mut(rect).with_wrap(wrap)
+    }
+}
+
+/// A view of the whole grid without wrapping.
+impl<'a, T> From<&'a Grid<T>> for GridView<'a, T> {
+    fn from(grid: &'a Grid<T>) -> Self {
+        grid.as_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::{DirectionPattern, Repeat};
+
+    // 0, 1, 2, 3
+    // 4, 5, 6, 7
+    // 8, 9,10,11
+    //12,13,14,15
+    fn grid_4x4() -> Grid<usize> {
+        Grid {
+            width: 4,
+            height: 4,
+            items: (0..16).collect(),
+        }
+    }
+
+    #[test]
+    fn view_local_positions() {
+        let grid = grid_4x4();
+        let view = grid.view(Rect::new((1, 2), 3, 2));
+
+        let mut row = view.row(1).grid_positions();
+        assert_eq!(row.next(), Some(((0, 1).into(), &13)));
+        assert_eq!(row.next(), Some(((1, 1).into(), &14)));
+        assert_eq!(row.next(), Some(((2, 1).into(), &15)));
+        assert_eq!(row.next(), None);
+
+        let mut column = view.column(2).grid_positions();
+        assert_eq!(column.next(), Some(((2, 0).into(), &11)));
+        assert_eq!(column.next(), Some(((2, 1).into(), &15)));
+        assert_eq!(column.next(), None);
+
+        let mut neighbors = view.neighbors((0, 0)).grid_positions();
+        assert_eq!(neighbors.next(), Some(((1, 0).into(), &10)));
+        assert_eq!(neighbors.next(), Some(((0, 1).into(), &13)));
+        assert_eq!(neighbors.next(), Some(((1, 1).into(), &14)));
+        assert_eq!(neighbors.next(), None);
+        assert_eq!(view.to_parent((1, 1)), Position::new(2, 3));
+    }
+
+    #[test]
+    fn nested_view() {
+        let grid = grid_4x4();
+        let view = grid.view(Rect::new((1, 1), 3, 3));
+        let inner = view.view(Rect::new((1, 1), 2, 2));
+        assert_eq!(inner.rect(), Rect::new((2, 2), 2, 2));
+        assert_eq!(inner.get((0, 0)), Some(&10));
+        assert_eq!(inner.get((1, 1)), Some(&15));
+        assert_eq!(inner.get((2, 0)), None);
+    }
+
+    #[test]
+    fn view_pattern_stops_at_view_border() {
+        let grid = grid_4x4();
+        let view = grid.view(Rect::new((1, 1), 2, 3));
+        let pattern = DirectionPattern::new((0, 1), Repeat::TillEnd);
+        let mut iter = view.pattern((1, 0), pattern).grid_positions();
+        assert_eq!(iter.next(), Some(((1, 1).into(), &10)));
+        assert_eq!(iter.next(), Some(((1, 2).into(), &14)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_out_of_bounds() {
+        let grid = grid_4x4();
+        grid.view(Rect::new((2, 2), 3, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "rect is out of bounds")]
+    fn view_rect_overflowing_usize() {
+        let mut grid = grid_4x4();
+        grid.view_mut(Rect::new((usize::MAX, 1), 2, 1));
+    }
+
+    #[test]
+    fn split_view_mut_into_quadrants() {
+        let mut grid = grid_4x4();
+        let view = grid.view_mut(Rect::new((0, 0), 4, 4));
+        let (top, bottom) = view.split_at_row(2);
+        let (mut top_left, mut top_right) = top.split_at_column(2);
+        let (mut bottom_left, mut bottom_right) = bottom.split_at_column(1);
+
+        assert_eq!(top_right.rect(), Rect::new((2, 0), 2, 2));
+        assert_eq!(bottom_right.rect(), Rect::new((1, 2), 3, 2));
+        top_left.neighbors_mut((0, 0)).for_each(|cell| *cell = 100);
+        top_right.column_mut(0).for_each(|cell| *cell = 200);
+        bottom_left.set((0, 1), 300);
+        bottom_right.row_mut(0).for_each(|cell| *cell = 400);
+
+        assert_eq!(
+            grid.items,
+            vec![0, 100, 200, 3, 100, 100, 200, 7, 8, 400, 400, 400, 300, 13, 14, 15]
+        );
+    }
+
+    #[test]
+    fn view_mut_read_only_iterators() {
+        let mut grid = grid_4x4();
+        let view = grid.view_mut(Rect::new((0, 0), 4, 4));
+        let (_, bottom) = view.split_at_row(2);
+        let (_, mut bottom_right) = bottom.split_at_column(1);
+
+        assert_eq!(bottom_right.column(2).collect::<Vec<_>>(), vec![&11, &15]);
+        assert_eq!(
+            bottom_right.neighbors((0, 0)).collect::<Vec<_>>(),
+            vec![&10, &13, &14]
+        );
+        let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd);
+        assert_eq!(
+            bottom_right.pattern((0, 1), pattern).collect::<Vec<_>>(),
+            vec![&14, &15]
+        );
+
+        let mut column = bottom_right.column_mut(1).grid_positions().rev();
+        assert_eq!(column.len(), 2);
+        assert_eq!(column.next(), Some(((1, 1).into(), &mut 14)));
+        assert_eq!(column.next(), Some(((1, 0).into(), &mut 10)));
+        assert_eq!(column.next(), None);
+
+        let view = grid.wrapping_mut(Wrap::Horizontal);
+        assert_eq!(
+            view.neighbors((0, 0)).collect::<Vec<_>>(),
+            vec![&3, &1, &7, &4, &5]
+        );
+    }
+
+    #[test]
+    fn view_mut_pattern_and_neighbors() {
+        let mut grid = grid_4x4();
+        let mut view = grid.view_mut(Rect::new((1, 1), 3, 3));
+
+        let pattern = DirectionPattern::new((1, 1), Repeat::TillEnd);
+        let mut iter = view.pattern_mut((0, 0), pattern).grid_positions();
+        assert_eq!(iter.next(), Some(((1, 1).into(), &mut 10)));
+        assert_eq!(iter.next(), Some(((2, 2).into(), &mut 15)));
+        assert_eq!(iter.next(), None);
+
+        let mut neighbors = view.neighbors_mut((2, 2)).grid_positions();
+        assert_eq!(neighbors.next(), Some(((1, 1).into(), &mut 10)));
+        assert_eq!(neighbors.next(), Some(((2, 1).into(), &mut 11)));
+        assert_eq!(neighbors.next(), Some(((1, 2).into(), &mut 14)));
+        assert_eq!(neighbors.next(), None);
+    }
+
+    #[test]
+    fn wrapping_neighbors() {
+        let grid = grid_4x4();
+        let torus = grid.wrapping(Wrap::Both);
+        let mut neighbors = torus.neighbors((0, 0)).grid_positions();
+        assert_eq!(neighbors.next(), Some(((3, 3).into(), &15)));
+        assert_eq!(neighbors.next(), Some(((0, 3).into(), &12)));
+        assert_eq!(neighbors.next(), Some(((1, 3).into(), &13)));
+        assert_eq!(neighbors.next(), Some(((3, 0).into(), &3)));
+        assert_eq!(neighbors.next(), Some(((1, 0).into(), &1)));
+        assert_eq!(neighbors.next(), Some(((3, 1).into(), &7)));
+        assert_eq!(neighbors.next(), Some(((0, 1).into(), &4)));
+        assert_eq!(neighbors.next(), Some(((1, 1).into(), &5)));
+        assert_eq!(neighbors.next(), None);
+
+        let cylinder = grid.wrapping(Wrap::Vertical);
+        let neighbors: Vec<_> = cylinder.neighbors((3, 3)).collect();
+        assert_eq!(neighbors, vec![&10, &11, &14, &2, &3]);
+    }
+
+    #[test]
+    fn wrapping_neighbors_are_distinct() {
+        let grid = Grid {
+            width: 2,
+            height: 2,
+            items: (0..4).collect(),
+        };
+        let neighbors: Vec<_> = grid.wrapping(Wrap::Both).neighbors((0, 0)).collect();
+        assert_eq!(neighbors, vec![&3, &2, &1]);
+
+        let mut grid = Grid::new(1, 3, 0);
+        grid.wrapping_mut(Wrap::Both)
+            .neighbors_mut((0, 1))
+            .for_each(|cell| *cell += 1);
+        assert_eq!(grid.items, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn wrapping_large_neighborhood() {
+        let grid = Grid::new(5, 4, 0);
+        let view = grid.wrapping(Wrap::Both);
+        let neighborhood = Neighborhood::moore_radius(200);
+        assert_eq!(view.neighbors_with((2, 1), &neighborhood).count(), 19);
+
+        let repeated = Neighborhood::custom(vec![(1, 0), (1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors_with((0, 0), &repeated).count(), 2);
+
+        let grid = Grid::new(3, 3, 0);
+        let torus = grid.wrapping(Wrap::Both);
+        assert_eq!(torus.neighbors((1, 1)).count(), 8);
+        // (1, 0) and (4, 0) reach the same cell, (3, 0) returns to the position itself
+        let long_steps = Neighborhood::custom(vec![(1, 0), (3, 0), (4, 0)]);
+        let cylinder = grid.wrapping(Wrap::Horizontal);
+        assert_eq!(cylinder.neighbors_with((0, 0), &long_steps).count(), 1);
+    }
+
+    #[test]
+    fn wrapping_view_mut_pattern() {
+        let mut grid = grid_4x4();
+        let mut view = grid
+            .view_mut(Rect::new((1, 1), 2, 2))
+            .with_wrap(Wrap::Horizontal);
+
+        let pattern = DirectionPattern::new((1, 0), Repeat::TillEnd);
+        let mut iter = view.pattern_mut((1, 0), pattern).grid_positions();
+        assert_eq!(iter.next(), Some(((0, 0).into(), &mut 5)));
+        assert_eq!(iter.next(), None);
+    }
+}